@@ -0,0 +1,146 @@
+//! RPC interface for the voting pallet.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+pub use pallet_voting_runtime_api::VotingApi as VotingRuntimeApi;
+
+/// Maximum number of finished proposals returned in one page.
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+#[rpc(client, server)]
+pub trait VotingApi<BlockHash, AccountId, ActiveProposal, FinishedProposal> {
+	/// Votes of a registered voter, including the votes delegated to it.
+	#[method(name = "voting_votePower")]
+	fn vote_power(&self, who: AccountId, at: Option<BlockHash>) -> RpcResult<Option<u128>>;
+
+	/// Ids of the open proposals.
+	#[method(name = "voting_activeProposalIds")]
+	fn active_proposal_ids(&self, at: Option<BlockHash>) -> RpcResult<Vec<u32>>;
+
+	/// An open proposal.
+	#[method(name = "voting_activeProposal")]
+	fn active_proposal(&self, id: u32, at: Option<BlockHash>) -> RpcResult<Option<ActiveProposal>>;
+
+	/// Up to `count` finished proposals, starting from the proposal id `start`. The page size is
+	/// limited to `MAX_PAGE_SIZE`.
+	#[method(name = "voting_finishedProposals")]
+	fn finished_proposals(
+		&self,
+		start: u32,
+		count: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<FinishedProposal>>;
+
+	/// Whether the account has voted in the proposal.
+	#[method(name = "voting_hasVoted")]
+	fn has_voted(&self, who: AccountId, proposal_id: u32, at: Option<BlockHash>) -> RpcResult<bool>;
+}
+
+/// Provides RPC methods to query the voting pallet.
+pub struct Voting<C, P> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> Voting<C, P> {
+	/// Creates a new instance of the voting RPC.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+fn runtime_error(message: &'static str, e: sp_api::ApiError) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(Error::RuntimeError.into(), message, Some(e.to_string())))
+		.into()
+}
+
+impl<C, Block, AccountId, ActiveProposal, FinishedProposal>
+	VotingApiServer<<Block as BlockT>::Hash, AccountId, ActiveProposal, FinishedProposal>
+	for Voting<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: VotingRuntimeApi<Block, AccountId, ActiveProposal, FinishedProposal>,
+	AccountId: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+	ActiveProposal: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+	FinishedProposal: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+	fn vote_power(&self, who: AccountId, at: Option<Block::Hash>) -> RpcResult<Option<u128>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.vote_power(at_hash, who)
+			.map_err(|e| runtime_error("Unable to query the vote power.", e))
+	}
+
+	fn active_proposal_ids(&self, at: Option<Block::Hash>) -> RpcResult<Vec<u32>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.active_proposal_ids(at_hash)
+			.map_err(|e| runtime_error("Unable to query the active proposal ids.", e))
+	}
+
+	fn active_proposal(
+		&self,
+		id: u32,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<ActiveProposal>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.active_proposal(at_hash, id)
+			.map_err(|e| runtime_error("Unable to query the active proposal.", e))
+	}
+
+	fn finished_proposals(
+		&self,
+		start: u32,
+		count: u32,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<FinishedProposal>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.finished_proposals(at_hash, start, count.min(MAX_PAGE_SIZE))
+			.map_err(|e| runtime_error("Unable to query the finished proposals.", e))
+	}
+
+	fn has_voted(
+		&self,
+		who: AccountId,
+		proposal_id: u32,
+		at: Option<Block::Hash>,
+	) -> RpcResult<bool> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.has_voted(at_hash, who, proposal_id)
+			.map_err(|e| runtime_error("Unable to query whether the account has voted.", e))
+	}
+}
@@ -0,0 +1,26 @@
+//! Runtime API definition for the voting pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	/// Query the voters and the proposals of the voting pallet.
+	pub trait VotingApi<AccountId, ActiveProposal, FinishedProposal> where
+		AccountId: Codec,
+		ActiveProposal: Codec,
+		FinishedProposal: Codec,
+	{
+		/// Votes of a registered voter, including the votes delegated to it.
+		fn vote_power(who: AccountId) -> Option<u128>;
+		/// Ids of the open proposals.
+		fn active_proposal_ids() -> Vec<u32>;
+		/// An open proposal.
+		fn active_proposal(id: u32) -> Option<ActiveProposal>;
+		/// Up to `count` finished proposals, starting from the proposal id `start`.
+		fn finished_proposals(start: u32, count: u32) -> Vec<FinishedProposal>;
+		/// Whether the account has voted in the proposal.
+		fn has_voted(who: AccountId, proposal_id: u32) -> bool;
+	}
+}
@@ -0,0 +1,89 @@
+//! Fungible assets that give the vote power of the asset proposals.
+
+use frame_support::{
+	dispatch::DispatchResult,
+	sp_runtime::{DispatchError, TokenError},
+	traits::tokens::{fungibles, AssetId, Balance, DepositConsequence, WithdrawConsequence},
+};
+use sp_std::marker::PhantomData;
+
+/// Helper to create a funded asset in the benchmarks.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<AssetId, AccountId, Balance> {
+	/// An asset with `amount` free tokens of `who`, `None` when the runtime has no assets.
+	fn funded_asset(who: &AccountId, amount: Balance) -> Option<AssetId>;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl<AssetId, AccountId, Balance> BenchmarkHelper<AssetId, AccountId, Balance> for () {
+	fn funded_asset(_: &AccountId, _: Balance) -> Option<AssetId> {
+		None
+	}
+}
+
+/// No assets: every asset is unknown and nothing can be held, so the runtime only has proposals
+/// of the native currency.
+pub struct NoAssets<A, B>(PhantomData<(A, B)>);
+
+impl<A: AssetId, B: Balance, AccountId> fungibles::Inspect<AccountId> for NoAssets<A, B> {
+	type AssetId = A;
+	type Balance = B;
+
+	fn total_issuance(_: A) -> B {
+		B::default()
+	}
+
+	fn minimum_balance(_: A) -> B {
+		B::default()
+	}
+
+	fn balance(_: A, _: &AccountId) -> B {
+		B::default()
+	}
+
+	fn reducible_balance(_: A, _: &AccountId, _: bool) -> B {
+		B::default()
+	}
+
+	fn can_deposit(_: A, _: &AccountId, _: B, _: bool) -> DepositConsequence {
+		DepositConsequence::UnknownAsset
+	}
+
+	fn can_withdraw(_: A, _: &AccountId, _: B) -> WithdrawConsequence<B> {
+		WithdrawConsequence::UnknownAsset
+	}
+
+	fn asset_exists(_: A) -> bool {
+		false
+	}
+}
+
+impl<A: AssetId, B: Balance, AccountId> fungibles::InspectHold<AccountId> for NoAssets<A, B> {
+	fn balance_on_hold(_: A, _: &AccountId) -> B {
+		B::default()
+	}
+
+	fn can_hold(_: A, _: &AccountId, _: B) -> bool {
+		false
+	}
+}
+
+impl<A: AssetId, B: Balance, AccountId> fungibles::Transfer<AccountId> for NoAssets<A, B> {
+	fn transfer(_: A, _: &AccountId, _: &AccountId, _: B, _: bool) -> Result<B, DispatchError> {
+		Err(TokenError::UnknownAsset.into())
+	}
+}
+
+impl<A: AssetId, B: Balance, AccountId> fungibles::MutateHold<AccountId> for NoAssets<A, B> {
+	fn hold(_: A, _: &AccountId, _: B) -> DispatchResult {
+		Err(TokenError::UnknownAsset.into())
+	}
+
+	fn release(_: A, _: &AccountId, _: B, _: bool) -> Result<B, DispatchError> {
+		Err(TokenError::UnknownAsset.into())
+	}
+
+	fn transfer_held(_: A, _: &AccountId, _: &AccountId, _: B, _: bool, _: bool) -> Result<B, DispatchError> {
+		Err(TokenError::UnknownAsset.into())
+	}
+}
@@ -4,16 +4,24 @@ use super::*;
 
 #[allow(unused)]
 use crate::Pallet as Voting;
-use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_benchmarking::{account, benchmarks, whitelisted_caller, BenchmarkError};
+use crate::assets::BenchmarkHelper;
 use frame_system::RawOrigin;
 use frame_support::{
 	ensure,
 	pallet_prelude::DispatchResultWithPostInfo,
-	sp_runtime::traits::{Bounded, Get, Hash},
+	sp_runtime::traits::{Bounded, Get, Hash, One, Zero},
+	traits::{LockableCurrency, WithdrawReasons},
 	BoundedVec
 };
 use frame_benchmarking::Vec;
 
+/// Move to the next block, so the votes got in the current block count in the proposals created
+/// from now on.
+fn next_block<T: Config>() {
+	let now = frame_system::Pallet::<T>::block_number();
+	frame_system::Pallet::<T>::set_block_number(now + One::one());
+}
 
 benchmarks! {
 
@@ -46,9 +54,18 @@ benchmarks! {
 
 		let options_bounded: BoundedVec<_, _> = options_vec.try_into().unwrap();
 
-	}: _(RawOrigin::Root, text, options_bounded)
+		// attach a call to every option
+		let mut calls_vec: Vec<(u8, BoundedCallOf<T>)> = Vec::new();
+		for i in 0..T::MaxVecLen::get() {
+			let call: <T as Config>::RuntimeCall = frame_system::Call::<T>::remark { remark: Vec::new() }.into();
+			calls_vec.push((i as u8, T::Preimages::bound(call)?));
+		}
+
+		let calls_bounded: BoundedVec<_, _> = calls_vec.try_into().unwrap();
+
+	}: _(RawOrigin::Root, text, options_bounded, BallotType::Split, false, Thresholds::default(), None, calls_bounded, None, None, None)
 	verify {
-		assert!(ActiveProposal::<T>::get().is_some());
+		assert!(ActiveProposals::<T>::contains_key(1));
 	}
 
 	vote {
@@ -58,6 +75,7 @@ benchmarks! {
 		T::Currency::make_free_balance_be(&caller, balance);
 		Pallet::<T>::add_voter(RawOrigin::Signed(caller.clone()).into());
 		Pallet::<T>::get_votes(RawOrigin::Signed(caller.clone()).into(), balance - T::RegisterFee::get())?;
+		next_block::<T>();
 
 		let mut votes_vec: Vec<VoteStruct> = Vec::new();
 
@@ -75,16 +93,164 @@ benchmarks! {
 		}
 
 		let options_bounded: BoundedVec<_, _> = options_vec.try_into().unwrap();
-		Pallet::<T>::set_proposal(RawOrigin::Root.into(), text, options_bounded)?;
-	}: _(RawOrigin::Signed(caller.clone()), votes_bouded)
+		Pallet::<T>::set_proposal(RawOrigin::Root.into(), text, options_bounded, BallotType::Split, false, Thresholds::default(), None, BoundedVec::default(), None, None, None)?;
+	}: _(RawOrigin::Signed(caller.clone()), 1, votes_bouded, Conviction::Locked6x)
 	verify {
-		assert!(VotedProposals::<T>::contains_key(caller.clone()));
+		assert!(VotedProposals::<T>::contains_key(1, caller.clone()));
 	}
 
 	end_proposal {
+		let o in 2 .. T::MaxVecLen::get();
+		let r in 0 .. T::MaxRankings::get();
 		let caller : T::AccountId = whitelisted_caller();
 
-		//create proposal
+		//create a single transferable vote proposal for one seat, every ranking lists all the
+		//options and one option is eliminated per round
+		let mut options_vec: Vec<Options<T::Hash>> = Vec::new();
+		let text = T::Hashing::hash_of(&1);
+		for i in 0..o {
+			options_vec.push(Options {id: i as u8, votes: 0, text: text});
+		}
+
+		let options_bounded: BoundedVec<_, _> = options_vec.try_into().unwrap();
+		Pallet::<T>::set_proposal(RawOrigin::Root.into(), text, options_bounded, BallotType::SingleTransferable(1), false, Thresholds::default(), None, BoundedVec::default(), None, None, None)?;
+
+		let mut ballots: Vec<RankedBallot<T>> = Vec::new();
+		for i in 0..r {
+			let ranking: Vec<u8> = (0..o).map(|j| ((i + j) % o) as u8).collect();
+			ballots.push(RankedBallot { ranking: ranking.try_into().unwrap(), votes: 1 });
+		}
+		let ballots: BoundedVec<_, _> = ballots.try_into().unwrap();
+		RankedBallots::<T>::insert(1, ballots);
+
+		let end_block = ActiveProposals::<T>::get(1).unwrap().end_block;
+		frame_system::Pallet::<T>::set_block_number(end_block + One::one());
+	}: _(RawOrigin::Signed(caller.clone()), 1)
+	verify {
+		assert!(!ActiveProposals::<T>::contains_key(1));
+	}
+
+	vote_ranked {
+		let caller : T::AccountId = whitelisted_caller();
+		let balance = BalanceOf::<T>::max_value();
+		T::Currency::make_free_balance_be(&caller, balance);
+		Pallet::<T>::add_voter(RawOrigin::Signed(caller.clone()).into());
+		Pallet::<T>::get_votes(RawOrigin::Signed(caller.clone()).into(), balance - T::RegisterFee::get())?;
+		next_block::<T>();
+
+		//create proposal and rank all of its options
+		let mut options_vec: Vec<Options<T::Hash>> = Vec::new();
+		let mut ranking_vec: Vec<u8> = Vec::new();
+		let text = T::Hashing::hash_of(&1);
+		for i in 0..T::MaxVecLen::get() {
+			options_vec.push(Options {id: i as u8, votes: 0, text: text});
+			ranking_vec.push(i as u8);
+		}
+
+		let options_bounded: BoundedVec<_, _> = options_vec.try_into().unwrap();
+		let ranking_bounded: BoundedVec<_, _> = ranking_vec.try_into().unwrap();
+		Pallet::<T>::set_proposal(RawOrigin::Root.into(), text, options_bounded, BallotType::RankedChoice, false, Thresholds::default(), None, BoundedVec::default(), None, None, None)?;
+	}: _(RawOrigin::Signed(caller.clone()), 1, ranking_bounded, Conviction::Locked6x)
+	verify {
+		assert!(VotedProposals::<T>::contains_key(1, caller.clone()));
+	}
+
+	commit_vote {
+		let caller : T::AccountId = whitelisted_caller();
+		let balance = BalanceOf::<T>::max_value();
+		T::Currency::make_free_balance_be(&caller, balance);
+		Pallet::<T>::add_voter(RawOrigin::Signed(caller.clone()).into());
+		Pallet::<T>::get_votes(RawOrigin::Signed(caller.clone()).into(), balance - T::RegisterFee::get())?;
+		next_block::<T>();
+
+		//create secret proposal
+		let mut options_vec: Vec<Options<T::Hash>> = Vec::new();
+		let text = T::Hashing::hash_of(&1);
+		for i in 0..T::MaxVecLen::get() {
+			options_vec.push(Options {id: i as u8, votes: 0, text: text});
+		}
+
+		let options_bounded: BoundedVec<_, _> = options_vec.try_into().unwrap();
+		Pallet::<T>::set_proposal(RawOrigin::Root.into(), text, options_bounded, BallotType::Split, true, Thresholds::default(), None, BoundedVec::default(), None, None, None)?;
+	}: _(RawOrigin::Signed(caller.clone()), 1, text)
+	verify {
+		assert!(Commitments::<T>::contains_key(caller.clone(), 1));
+	}
+
+	reveal_vote {
+		let caller : T::AccountId = whitelisted_caller();
+		let balance = BalanceOf::<T>::max_value();
+		T::Currency::make_free_balance_be(&caller, balance);
+		Pallet::<T>::add_voter(RawOrigin::Signed(caller.clone()).into());
+		Pallet::<T>::get_votes(RawOrigin::Signed(caller.clone()).into(), balance - T::RegisterFee::get())?;
+		next_block::<T>();
+
+		let mut votes_vec: Vec<VoteStruct> = Vec::new();
+
+		for i in 0..T::MaxVecLen::get() {
+			votes_vec.push(VoteStruct {id: i as u8, votes: 1000});
+		}
+
+		let votes_bouded: BoundedVec<_, _> = votes_vec.try_into().unwrap();
+		let salt = [7u8; 32];
+
+		//create secret proposal and commit the votes
+		let mut options_vec: Vec<Options<T::Hash>> = Vec::new();
+		let text = T::Hashing::hash_of(&1);
+		for i in 0..T::MaxVecLen::get() {
+			options_vec.push(Options {id: i as u8, votes: 0, text: text});
+		}
+
+		let options_bounded: BoundedVec<_, _> = options_vec.try_into().unwrap();
+		Pallet::<T>::set_proposal(RawOrigin::Root.into(), text, options_bounded, BallotType::Split, true, Thresholds::default(), None, BoundedVec::default(), None, None, None)?;
+		Pallet::<T>::commit_vote(RawOrigin::Signed(caller.clone()).into(), 1, T::Hashing::hash_of(&(votes_bouded.clone(), salt)))?;
+
+		let reveal_block = frame_system::Pallet::<T>::block_number() + T::MaxProposalDuration::get() + 1u32.into();
+		frame_system::Pallet::<T>::set_block_number(reveal_block);
+	}: _(RawOrigin::Signed(caller.clone()), 1, votes_bouded, salt)
+	verify {
+		assert!(!Commitments::<T>::contains_key(caller.clone(), 1));
+	}
+
+	reveal_ranked {
+		let caller : T::AccountId = whitelisted_caller();
+		let balance = BalanceOf::<T>::max_value();
+		T::Currency::make_free_balance_be(&caller, balance);
+		Pallet::<T>::add_voter(RawOrigin::Signed(caller.clone()).into());
+		Pallet::<T>::get_votes(RawOrigin::Signed(caller.clone()).into(), balance - T::RegisterFee::get())?;
+		next_block::<T>();
+
+		//create secret proposal and commit a ranking of all of its options
+		let mut options_vec: Vec<Options<T::Hash>> = Vec::new();
+		let mut ranking_vec: Vec<u8> = Vec::new();
+		let text = T::Hashing::hash_of(&1);
+		for i in 0..T::MaxVecLen::get() {
+			options_vec.push(Options {id: i as u8, votes: 0, text: text});
+			ranking_vec.push(i as u8);
+		}
+
+		let options_bounded: BoundedVec<_, _> = options_vec.try_into().unwrap();
+		let ranking_bounded: BoundedVec<_, _> = ranking_vec.try_into().unwrap();
+		let salt = [7u8; 32];
+		Pallet::<T>::set_proposal(RawOrigin::Root.into(), text, options_bounded, BallotType::RankedChoice, true, Thresholds::default(), None, BoundedVec::default(), None, None, None)?;
+		Pallet::<T>::commit_vote(RawOrigin::Signed(caller.clone()).into(), 1, T::Hashing::hash_of(&(ranking_bounded.clone(), salt)))?;
+
+		let reveal_block = frame_system::Pallet::<T>::block_number() + T::MaxProposalDuration::get() + 1u32.into();
+		frame_system::Pallet::<T>::set_block_number(reveal_block);
+	}: _(RawOrigin::Signed(caller.clone()), 1, ranking_bounded, salt)
+	verify {
+		assert!(!Commitments::<T>::contains_key(caller.clone(), 1));
+	}
+
+	slash_unrevealed {
+		let caller : T::AccountId = whitelisted_caller();
+		let balance = BalanceOf::<T>::max_value();
+		T::Currency::make_free_balance_be(&caller, balance);
+		Pallet::<T>::add_voter(RawOrigin::Signed(caller.clone()).into());
+		Pallet::<T>::get_votes(RawOrigin::Signed(caller.clone()).into(), balance - T::RegisterFee::get())?;
+		next_block::<T>();
+
+		//create secret proposal, commit and end it without revealing
 		let mut options_vec: Vec<Options<T::Hash>> = Vec::new();
 		let text = T::Hashing::hash_of(&1);
 		for i in 0..T::MaxVecLen::get() {
@@ -92,12 +258,174 @@ benchmarks! {
 		}
 
 		let options_bounded: BoundedVec<_, _> = options_vec.try_into().unwrap();
-		Pallet::<T>::set_proposal(RawOrigin::Root.into(), text, options_bounded)?;
+		Pallet::<T>::set_proposal(RawOrigin::Root.into(), text, options_bounded, BallotType::Split, true, Thresholds::default(), None, BoundedVec::default(), None, None, None)?;
+		Pallet::<T>::commit_vote(RawOrigin::Signed(caller.clone()).into(), 1, text)?;
+
+		let closing_block = frame_system::Pallet::<T>::block_number() + T::MaxProposalDuration::get() + T::RevealPeriod::get();
+		frame_system::Pallet::<T>::set_block_number(closing_block);
+		Pallet::<T>::end_proposal(RawOrigin::Signed(caller.clone()).into(), 1)?;
+	}: _(RawOrigin::Signed(caller.clone()), 1, caller.clone())
+	verify {
+		assert!(!Commitments::<T>::contains_key(caller.clone(), 1));
+	}
+
+	delegate {
+		let caller : T::AccountId = whitelisted_caller();
+		let delegate : T::AccountId = account("delegate", 0, 0);
+		let balance = BalanceOf::<T>::max_value();
+		T::Currency::make_free_balance_be(&caller, balance);
+		T::Currency::make_free_balance_be(&delegate, balance);
+		Pallet::<T>::add_voter(RawOrigin::Signed(caller.clone()).into())?;
+		Pallet::<T>::add_voter(RawOrigin::Signed(delegate.clone()).into())?;
+	}: _(RawOrigin::Signed(caller.clone()), delegate.clone())
+	verify {
+		assert!(Delegations::<T>::get(caller) == Some(delegate));
+	}
+
+	undelegate {
+		let caller : T::AccountId = whitelisted_caller();
+		let delegate : T::AccountId = account("delegate", 0, 0);
+		let balance = BalanceOf::<T>::max_value();
+		T::Currency::make_free_balance_be(&caller, balance);
+		T::Currency::make_free_balance_be(&delegate, balance / 2u32.into());
+		Pallet::<T>::add_voter(RawOrigin::Signed(caller.clone()).into())?;
+		Pallet::<T>::get_votes(RawOrigin::Signed(caller.clone()).into(), balance - T::RegisterFee::get())?;
+		next_block::<T>();
+		Pallet::<T>::add_voter(RawOrigin::Signed(delegate.clone()).into())?;
+		Pallet::<T>::delegate(RawOrigin::Signed(caller.clone()).into(), delegate.clone())?;
+
+		// the delegate votes with the delegated votes in an open proposal
+		let mut options_vec: Vec<Options<T::Hash>> = Vec::new();
+		let text = T::Hashing::hash_of(&1);
+		for i in 0..T::MaxVecLen::get() {
+			options_vec.push(Options {id: i as u8, votes: 0, text: text});
+		}
 
-		frame_system::Pallet::<T>::set_block_number(15u32.into());
+		let options_bounded: BoundedVec<_, _> = options_vec.try_into().unwrap();
+		Pallet::<T>::set_proposal(RawOrigin::Root.into(), text, options_bounded, BallotType::Split, false, Thresholds::default(), None, BoundedVec::default(), None, None, None)?;
+		let mut votes_vec: Vec<VoteStruct> = Vec::new();
+		votes_vec.push(VoteStruct {id: 0, votes: 1000});
+		let votes_bouded: BoundedVec<_, _> = votes_vec.try_into().unwrap();
+		Pallet::<T>::vote(RawOrigin::Signed(delegate.clone()).into(), 1, votes_bouded, Conviction::None)?;
 	}: _(RawOrigin::Signed(caller.clone()))
 	verify {
-		assert!(ActiveProposal::<T>::get().is_none());
+		assert!(!Delegations::<T>::contains_key(caller));
+	}
+
+	resolve_tie {
+		let caller : T::AccountId = whitelisted_caller();
+		let balance = BalanceOf::<T>::max_value();
+		T::Currency::make_free_balance_be(&caller, balance);
+		Pallet::<T>::add_voter(RawOrigin::Signed(caller.clone()).into())?;
+		Pallet::<T>::get_votes(RawOrigin::Signed(caller.clone()).into(), balance - T::RegisterFee::get())?;
+		next_block::<T>();
+
+		// create a proposal with a call in every option and tie the first two options
+		let mut options_vec: Vec<Options<T::Hash>> = Vec::new();
+		let mut calls_vec: Vec<(u8, BoundedCallOf<T>)> = Vec::new();
+		let text = T::Hashing::hash_of(&1);
+		for i in 0..T::MaxVecLen::get() {
+			options_vec.push(Options {id: i as u8, votes: 0, text: text});
+			let call: <T as Config>::RuntimeCall = frame_system::Call::<T>::remark { remark: Vec::new() }.into();
+			calls_vec.push((i as u8, T::Preimages::bound(call)?));
+		}
+
+		let options_bounded: BoundedVec<_, _> = options_vec.try_into().unwrap();
+		let calls_bounded: BoundedVec<_, _> = calls_vec.try_into().unwrap();
+		Pallet::<T>::set_proposal(RawOrigin::Root.into(), text, options_bounded, BallotType::Split, false, Thresholds::default(), Some(TieBreak::Origin), calls_bounded, None, None, None)?;
+		let mut votes_vec: Vec<VoteStruct> = Vec::new();
+		votes_vec.push(VoteStruct {id: 0, votes: 1000});
+		votes_vec.push(VoteStruct {id: 1, votes: 1000});
+		let votes_bouded: BoundedVec<_, _> = votes_vec.try_into().unwrap();
+		Pallet::<T>::vote(RawOrigin::Signed(caller.clone()).into(), 1, votes_bouded, Conviction::None)?;
+
+		frame_system::Pallet::<T>::set_block_number(T::MaxProposalDuration::get() + 2u32.into());
+		Pallet::<T>::end_proposal(RawOrigin::Signed(caller.clone()).into(), 1)?;
+		let origin = T::TieBreakOrigin::try_successful_origin().map_err(|_| "cannot build the tie break origin")?;
+	}: {
+		Pallet::<T>::resolve_tie(origin, 1, 1)?;
+	}
+	verify {
+		assert!(FinishedProposals::<T>::get(1).unwrap().winner_index == 1);
+	}
+
+	unvote {
+		let caller : T::AccountId = whitelisted_caller();
+		let balance = BalanceOf::<T>::max_value();
+		T::Currency::make_free_balance_be(&caller, balance);
+		Pallet::<T>::add_voter(RawOrigin::Signed(caller.clone()).into())?;
+		Pallet::<T>::get_votes(RawOrigin::Signed(caller.clone()).into(), balance - T::RegisterFee::get())?;
+		next_block::<T>();
+
+		//create proposal and rank all of its options
+		let mut options_vec: Vec<Options<T::Hash>> = Vec::new();
+		let mut ranking_vec: Vec<u8> = Vec::new();
+		let text = T::Hashing::hash_of(&1);
+		for i in 0..T::MaxVecLen::get() {
+			options_vec.push(Options {id: i as u8, votes: 0, text: text});
+			ranking_vec.push(i as u8);
+		}
+
+		let options_bounded: BoundedVec<_, _> = options_vec.try_into().unwrap();
+		let ranking_bounded: BoundedVec<_, _> = ranking_vec.try_into().unwrap();
+		Pallet::<T>::set_proposal(RawOrigin::Root.into(), text, options_bounded, BallotType::RankedChoice, false, Thresholds::default(), None, BoundedVec::default(), None, None, None)?;
+		Pallet::<T>::vote_ranked(RawOrigin::Signed(caller.clone()).into(), 1, ranking_bounded, Conviction::None)?;
+	}: _(RawOrigin::Signed(caller.clone()), 1)
+	verify {
+		assert!(!CastVotes::<T>::contains_key(1, caller.clone()));
+	}
+
+	revote {
+		let caller : T::AccountId = whitelisted_caller();
+		let balance = BalanceOf::<T>::max_value();
+		T::Currency::make_free_balance_be(&caller, balance);
+		Pallet::<T>::add_voter(RawOrigin::Signed(caller.clone()).into())?;
+		Pallet::<T>::get_votes(RawOrigin::Signed(caller.clone()).into(), balance - T::RegisterFee::get())?;
+		next_block::<T>();
+
+		let mut votes_vec: Vec<VoteStruct> = Vec::new();
+		let mut options_vec: Vec<Options<T::Hash>> = Vec::new();
+		let text = T::Hashing::hash_of(&1);
+		for i in 0..T::MaxVecLen::get() {
+			options_vec.push(Options {id: i as u8, votes: 0, text: text});
+			votes_vec.push(VoteStruct {id: i as u8, votes: 1000});
+		}
+
+		let options_bounded: BoundedVec<_, _> = options_vec.try_into().unwrap();
+		let votes_bouded: BoundedVec<_, _> = votes_vec.try_into().unwrap();
+		Pallet::<T>::set_proposal(RawOrigin::Root.into(), text, options_bounded, BallotType::Split, false, Thresholds::default(), None, BoundedVec::default(), None, None, None)?;
+		Pallet::<T>::vote(RawOrigin::Signed(caller.clone()).into(), 1, votes_bouded.clone(), Conviction::None)?;
+	}: _(RawOrigin::Signed(caller.clone()), 1, votes_bouded, Conviction::Locked6x)
+	verify {
+		assert!(VotedProposals::<T>::get(1, caller.clone()) == Some(1000 * T::MaxVecLen::get() as u128));
+	}
+
+	revote_ranked {
+		let caller : T::AccountId = whitelisted_caller();
+		let balance = BalanceOf::<T>::max_value();
+		T::Currency::make_free_balance_be(&caller, balance);
+		Pallet::<T>::add_voter(RawOrigin::Signed(caller.clone()).into())?;
+		Pallet::<T>::get_votes(RawOrigin::Signed(caller.clone()).into(), balance - T::RegisterFee::get())?;
+		next_block::<T>();
+
+		//create proposal and rank all of its options
+		let mut options_vec: Vec<Options<T::Hash>> = Vec::new();
+		let mut ranking_vec: Vec<u8> = Vec::new();
+		let text = T::Hashing::hash_of(&1);
+		for i in 0..T::MaxVecLen::get() {
+			options_vec.push(Options {id: i as u8, votes: 0, text: text});
+			ranking_vec.push(i as u8);
+		}
+
+		let options_bounded: BoundedVec<_, _> = options_vec.try_into().unwrap();
+		let ranking_bounded: BoundedVec<_, _> = ranking_vec.clone().try_into().unwrap();
+		Pallet::<T>::set_proposal(RawOrigin::Root.into(), text, options_bounded, BallotType::RankedChoice, false, Thresholds::default(), None, BoundedVec::default(), None, None, None)?;
+		Pallet::<T>::vote_ranked(RawOrigin::Signed(caller.clone()).into(), 1, ranking_bounded, Conviction::None)?;
+		ranking_vec.reverse();
+		let reversed_bounded: BoundedVec<_, _> = ranking_vec.try_into().unwrap();
+	}: _(RawOrigin::Signed(caller.clone()), 1, reversed_bounded, Conviction::Locked6x)
+	verify {
+		assert!(RankedBallots::<T>::get(1).len() == 1);
 	}
 
 	withdraw {
@@ -111,6 +439,230 @@ benchmarks! {
 		assert!(!Voters::<T>::contains_key(caller.clone()));
 	}
 
+	unlock {
+		let caller : T::AccountId = whitelisted_caller();
+		let balance = BalanceOf::<T>::max_value();
+		T::Currency::make_free_balance_be(&caller, balance);
+		T::Currency::set_lock(VOTING_ID, &caller, balance, WithdrawReasons::all());
+		VoteLocks::<T>::insert(caller.clone(), (balance, T::BlockNumber::zero()));
+		frame_system::Pallet::<T>::set_block_number(One::one());
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(!VoteLocks::<T>::contains_key(caller.clone()));
+	}
+
+	submit_proposal {
+		let caller : T::AccountId = whitelisted_caller();
+		let balance = BalanceOf::<T>::max_value();
+		T::Currency::make_free_balance_be(&caller, balance);
+		Pallet::<T>::add_voter(RawOrigin::Signed(caller.clone()).into());
+
+		// attach a call to every option
+		let mut options_vec: Vec<Options<T::Hash>> = Vec::new();
+		let mut calls_vec: Vec<(u8, BoundedCallOf<T>)> = Vec::new();
+		let text = T::Hashing::hash_of(&1);
+		for i in 0..T::MaxVecLen::get() {
+			options_vec.push(Options {id: i as u8, votes: 0, text: text});
+			let call: <T as Config>::RuntimeCall = frame_system::Call::<T>::remark { remark: Vec::new() }.into();
+			calls_vec.push((i as u8, T::Preimages::bound(call)?));
+		}
+
+		let options_bounded: BoundedVec<_, _> = options_vec.try_into().unwrap();
+		let calls_bounded: BoundedVec<_, _> = calls_vec.try_into().unwrap();
+	}: _(RawOrigin::Signed(caller.clone()), text, options_bounded, BallotType::Split, false, Thresholds::default(), None, calls_bounded)
+	verify {
+		assert!(ProposalQueue::<T>::get().len() == 1);
+	}
+
+	cancel_queued_proposal {
+		let caller : T::AccountId = whitelisted_caller();
+		let balance = BalanceOf::<T>::max_value();
+		T::Currency::make_free_balance_be(&caller, balance);
+		Pallet::<T>::add_voter(RawOrigin::Signed(caller.clone()).into());
+
+		let mut options_vec: Vec<Options<T::Hash>> = Vec::new();
+		let text = T::Hashing::hash_of(&1);
+		for i in 0..T::MaxVecLen::get() {
+			options_vec.push(Options {id: i as u8, votes: 0, text: text});
+		}
+
+		let options_bounded: BoundedVec<_, _> = options_vec.try_into().unwrap();
+		Pallet::<T>::submit_proposal(RawOrigin::Signed(caller.clone()).into(), text, options_bounded, BallotType::Split, false, Thresholds::default(), None, BoundedVec::default())?;
+	}: _(RawOrigin::Root, 0)
+	verify {
+		assert!(ProposalQueue::<T>::get().is_empty());
+	}
+
+	second {
+		let caller : T::AccountId = whitelisted_caller();
+		let balance = BalanceOf::<T>::max_value();
+		T::Currency::make_free_balance_be(&caller, balance);
+		Pallet::<T>::add_voter(RawOrigin::Signed(caller.clone()).into());
+
+		let mut options_vec: Vec<Options<T::Hash>> = Vec::new();
+		let text = T::Hashing::hash_of(&1);
+		for i in 0..T::MaxVecLen::get() {
+			options_vec.push(Options {id: i as u8, votes: 0, text: text});
+		}
+
+		let options_bounded: BoundedVec<_, _> = options_vec.try_into().unwrap();
+		Pallet::<T>::submit_proposal(RawOrigin::Signed(caller.clone()).into(), text, options_bounded, BallotType::Split, false, Thresholds::default(), None, BoundedVec::default())?;
+	}: _(RawOrigin::Signed(caller.clone()), 0)
+	verify {
+		assert!(ProposalQueue::<T>::get()[0].seconds.len() == 1);
+	}
+
+	cancel_proposal {
+		// create proposal with a call in every option
+		let mut options_vec: Vec<Options<T::Hash>> = Vec::new();
+		let mut calls_vec: Vec<(u8, BoundedCallOf<T>)> = Vec::new();
+		let text = T::Hashing::hash_of(&1);
+		for i in 0..T::MaxVecLen::get() {
+			options_vec.push(Options {id: i as u8, votes: 0, text: text});
+			let call: <T as Config>::RuntimeCall = frame_system::Call::<T>::remark { remark: Vec::new() }.into();
+			calls_vec.push((i as u8, T::Preimages::bound(call)?));
+		}
+
+		let options_bounded: BoundedVec<_, _> = options_vec.try_into().unwrap();
+		let calls_bounded: BoundedVec<_, _> = calls_vec.try_into().unwrap();
+		Pallet::<T>::set_proposal(RawOrigin::Root.into(), text, options_bounded, BallotType::Split, false, Thresholds::default(), None, calls_bounded, None, None, None)?;
+		let origin = T::CancelOrigin::try_successful_origin().map_err(|_| "cannot build the cancel origin")?;
+	}: {
+		Pallet::<T>::cancel_proposal(origin, 1)?;
+	}
+	verify {
+		assert!(FinishedProposals::<T>::get(1).unwrap().status == ProposalStatus::Cancelled);
+	}
+
+	veto {
+		let text = T::Hashing::hash_of(&1);
+		let origin = T::VetoOrigin::try_successful_origin().map_err(|_| "cannot build the veto origin")?;
+	}: {
+		Pallet::<T>::veto(origin, text)?;
+	}
+	verify {
+		assert!(Blacklist::<T>::contains_key(text));
+	}
+
+	unbond {
+		let caller : T::AccountId = whitelisted_caller();
+		let balance = BalanceOf::<T>::max_value();
+		T::Currency::make_free_balance_be(&caller, balance);
+		Pallet::<T>::add_voter(RawOrigin::Signed(caller.clone()).into())?;
+		Pallet::<T>::get_votes(RawOrigin::Signed(caller.clone()).into(), balance - T::RegisterFee::get())?;
+		let amount = T::RegisterFee::get();
+	}: _(RawOrigin::Signed(caller.clone()), amount)
+	verify {
+		assert!(Unlocking::<T>::get(caller.clone()).len() == 1);
+	}
+
+	withdraw_unbonded {
+		let caller : T::AccountId = whitelisted_caller();
+		let balance = BalanceOf::<T>::max_value();
+		T::Currency::make_free_balance_be(&caller, balance);
+		Pallet::<T>::add_voter(RawOrigin::Signed(caller.clone()).into())?;
+		Pallet::<T>::get_votes(RawOrigin::Signed(caller.clone()).into(), balance - T::RegisterFee::get())?;
+		Pallet::<T>::unbond(RawOrigin::Signed(caller.clone()).into(), T::RegisterFee::get())?;
+		frame_system::Pallet::<T>::set_block_number(T::UnbondingPeriod::get());
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(!Unlocking::<T>::contains_key(caller.clone()));
+	}
+
+	top_up_rewards {
+		let amount = T::RegisterFee::get();
+	}: _(RawOrigin::Root, amount)
+	verify {
+		assert!(T::Currency::free_balance(&Pallet::<T>::reward_pot()) == amount);
+	}
+
+	claim_rewards {
+		let caller : T::AccountId = whitelisted_caller();
+		let balance = T::RegisterFee::get() * 1000u32.into();
+		T::Currency::make_free_balance_be(&caller, balance);
+		Pallet::<T>::add_voter(RawOrigin::Signed(caller.clone()).into())?;
+		Pallet::<T>::get_votes(RawOrigin::Signed(caller.clone()).into(), balance - T::RegisterFee::get())?;
+		next_block::<T>();
+
+		let mut options_vec: Vec<Options<T::Hash>> = Vec::new();
+		let text = T::Hashing::hash_of(&1);
+		for i in 0..T::MaxVecLen::get() {
+			options_vec.push(Options {id: i as u8, votes: 0, text: text});
+		}
+
+		let options_bounded: BoundedVec<_, _> = options_vec.try_into().unwrap();
+		Pallet::<T>::set_proposal(RawOrigin::Root.into(), text, options_bounded, BallotType::Split, false, Thresholds::default(), None, BoundedVec::default(), None, None, None)?;
+		let mut votes_vec: Vec<VoteStruct> = Vec::new();
+		votes_vec.push(VoteStruct {id: 0, votes: 1});
+		let votes_bounded: BoundedVec<_, _> = votes_vec.try_into().unwrap();
+		Pallet::<T>::vote(RawOrigin::Signed(caller.clone()).into(), 1, votes_bounded, Conviction::None)?;
+
+		let end_block = ActiveProposals::<T>::get(1).unwrap().end_block;
+		frame_system::Pallet::<T>::set_block_number(end_block + One::one());
+		Pallet::<T>::end_proposal(RawOrigin::Signed(caller.clone()).into(), 1)?;
+
+		// the whole reward goes to the only voter
+		let spent_votes = VotedProposals::<T>::get(1, caller.clone()).unwrap_or_default();
+		ProposalRewards::<T>::insert(1, (T::RegisterFee::get(), spent_votes));
+		T::Currency::make_free_balance_be(&Pallet::<T>::reward_pot(), T::RegisterFee::get() * 2u32.into());
+	}: _(RawOrigin::Signed(caller.clone()), 1)
+	verify {
+		assert!(ClaimedRewards::<T>::contains_key(1, caller));
+	}
+
+	reap_inactive_voter {
+		let caller : T::AccountId = whitelisted_caller();
+		let voter : T::AccountId = account("voter", 0, 0);
+		let balance = BalanceOf::<T>::max_value();
+		T::Currency::make_free_balance_be(&voter, balance);
+		Pallet::<T>::add_voter(RawOrigin::Signed(voter.clone()).into())?;
+		Pallet::<T>::get_votes(RawOrigin::Signed(voter.clone()).into(), balance - T::RegisterFee::get())?;
+		next_block::<T>();
+
+		let mut options_vec: Vec<Options<T::Hash>> = Vec::new();
+		let text = T::Hashing::hash_of(&1);
+		for i in 0..T::MaxVecLen::get() {
+			options_vec.push(Options {id: i as u8, votes: 0, text: text});
+		}
+		let options_bounded: BoundedVec<_, _> = options_vec.try_into().unwrap();
+
+		// the voter skips every recent proposal
+		for i in 0..=T::MaxMissedProposals::get() {
+			let proposal_id = ProposalCount::<T>::get();
+			Pallet::<T>::set_proposal(RawOrigin::Root.into(), T::Hashing::hash_of(&i), options_bounded.clone(), BallotType::Split, false, Thresholds::default(), None, BoundedVec::default(), None, None, None)?;
+			let end = frame_system::Pallet::<T>::block_number() + T::MaxProposalDuration::get() + One::one();
+			frame_system::Pallet::<T>::set_block_number(end);
+			Pallet::<T>::end_proposal(RawOrigin::Signed(caller.clone()).into(), proposal_id)?;
+		}
+	}: _(RawOrigin::Signed(caller.clone()), voter.clone())
+	verify {
+		assert!(!Voters::<T>::contains_key(voter));
+	}
+
+	get_asset_votes {
+		let caller : T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		Pallet::<T>::add_voter(RawOrigin::Signed(caller.clone()).into())?;
+		let amount: AssetBalanceOf<T> = 1000u32.into();
+		// skipped in the runtimes without assets
+		let asset = T::AssetBenchmarkHelper::funded_asset(&caller, amount).ok_or(BenchmarkError::Skip)?;
+	}: _(RawOrigin::Signed(caller.clone()), asset, amount)
+	verify {
+		assert!(AssetStakes::<T>::get(caller, asset) == amount);
+	}
+
+	release_asset_votes {
+		let caller : T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		Pallet::<T>::add_voter(RawOrigin::Signed(caller.clone()).into())?;
+		let amount: AssetBalanceOf<T> = 1000u32.into();
+		let asset = T::AssetBenchmarkHelper::funded_asset(&caller, amount).ok_or(BenchmarkError::Skip)?;
+		Pallet::<T>::get_asset_votes(RawOrigin::Signed(caller.clone()).into(), asset, amount)?;
+	}: _(RawOrigin::Signed(caller.clone()), asset)
+	verify {
+		assert!(!AssetStakes::<T>::contains_key(caller, asset));
+	}
+
 
 	impl_benchmark_test_suite!(Voting, crate::mock::new_test_ext(), crate::mock::Test);
 }
@@ -0,0 +1,68 @@
+//! Curves that map the tokens reserved by a voter to their votes.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{sp_runtime::traits::IntegerSquareRoot, traits::Get};
+use scale_info::TypeInfo;
+use sp_std::marker::PhantomData;
+
+/// Identifier of a vote power curve, recorded in the events.
+#[derive(Debug, Clone, Copy, Encode, Decode, TypeInfo, MaxEncodedLen, Eq, PartialEq)]
+pub enum Curve {
+	Linear,
+	SquareRoot,
+	Logarithmic,
+	CappedLinear,
+}
+
+/// Mapping of the reserved tokens, without the register fee, to votes.
+pub trait VotePowerCurve {
+	/// Identifier of the curve.
+	const CURVE: Curve;
+
+	/// Votes given by `stake` tokens.
+	fn votes(stake: u128) -> u128;
+}
+
+/// One vote per token.
+pub struct Linear;
+
+impl VotePowerCurve for Linear {
+	const CURVE: Curve = Curve::Linear;
+
+	fn votes(stake: u128) -> u128 {
+		stake
+	}
+}
+
+/// The square root of the tokens, quadratic voting.
+pub struct SquareRoot;
+
+impl VotePowerCurve for SquareRoot {
+	const CURVE: Curve = Curve::SquareRoot;
+
+	fn votes(stake: u128) -> u128 {
+		stake.integer_sqrt()
+	}
+}
+
+/// The base 2 logarithm of the tokens plus one, rounded down.
+pub struct Logarithmic;
+
+impl VotePowerCurve for Logarithmic {
+	const CURVE: Curve = Curve::Logarithmic;
+
+	fn votes(stake: u128) -> u128 {
+		(u128::BITS - 1 - stake.saturating_add(1).leading_zeros()).into()
+	}
+}
+
+/// One vote per token up to `Cap` votes.
+pub struct CappedLinear<Cap>(PhantomData<Cap>);
+
+impl<Cap: Get<u128>> VotePowerCurve for CappedLinear<Cap> {
+	const CURVE: Curve = Curve::CappedLinear;
+
+	fn votes(stake: u128) -> u128 {
+		stake.min(Cap::get())
+	}
+}
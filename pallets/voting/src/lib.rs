@@ -5,7 +5,20 @@
 /// <https://docs.substrate.io/reference/frame-pallets/>
 pub use pallet::*;
 
-use frame_support::traits::Currency;
+use frame_support::traits::{Currency, LockIdentifier, OnUnbalanced};
+use sp_std::marker::PhantomData;
+
+/// Identifier of the locks of the stake backing the conviction votes.
+pub const VOTING_ID: LockIdentifier = *b"voting  ";
+
+/// Pays the slashed tokens to the reward pot, for example as the `InactivitySlashHandler`.
+pub struct RewardPot<T>(PhantomData<T>);
+
+impl<T: Config> OnUnbalanced<NegativeImbalanceOf<T>> for RewardPot<T> {
+	fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<T>) {
+		T::Currency::resolve_creating(&Pallet::<T>::reward_pot(), amount);
+	}
+}
 
 #[cfg(test)]
 mod mock;
@@ -16,24 +29,62 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod assets;
+pub mod curve;
+pub mod migrations;
 pub mod weights;
 
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
+	use crate::curve::{Curve, VotePowerCurve};
 	use frame_support::{
+		dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo},
 		pallet_prelude::{ *},
-		sp_runtime::traits::{IntegerSquareRoot, Zero},
-		traits::{Currency, LockableCurrency, ReservableCurrency},
+		sp_runtime::{
+			helpers_128bit::multiply_by_rational_with_rounding,
+			traits::{
+				AccountIdConversion, Hash, One, SaturatedConversion, Saturating, TrailingZeroInput,
+				Zero,
+			},
+			ArithmeticError, Perbill, Rounding,
+		},
+		traits::{
+			fungibles, Bounded, Currency, ExistenceRequirement, LockableCurrency,
+			Imbalance, NamedReservableCurrency, OnUnbalanced, QueryPreimage, Randomness,
+			StorePreimage, WithdrawReasons,
+		},
+		PalletId,
 	};
 	use frame_system::pallet_prelude::*;
 	use frame_system::weights::WeightInfo;
+	use sp_std::{vec, vec::Vec};
+	#[cfg(feature = "std")]
+	use serde::{Deserialize, Serialize};
 
 	pub type BalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::NegativeImbalance;
+	pub type AssetIdOf<T> = <<T as Config>::Assets as fungibles::Inspect<
+		<T as frame_system::Config>::AccountId,
+	>>::AssetId;
+	pub type AssetBalanceOf<T> = <<T as Config>::Assets as fungibles::Inspect<
+		<T as frame_system::Config>::AccountId,
+	>>::Balance;
+	pub type ReserveIdOf<T> = <<T as Config>::Currency as NamedReservableCurrency<
+		<T as frame_system::Config>::AccountId,
+	>>::ReserveIdentifier;
+
+	pub type BoundedCallOf<T> = Bounded<<T as Config>::RuntimeCall>;
+
+	/// The current version of the storage, bumped by every migration.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
@@ -42,25 +93,198 @@ pub mod pallet {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
+		/// The runtime origin, the calls of the winner options are dispatched with the voting origin.
+		type RuntimeOrigin: From<RawOrigin>;
+
+		/// The runtime call that can be attached to the proposal options.
+		type RuntimeCall: Parameter
+			+ Dispatchable<RuntimeOrigin = <Self as Config>::RuntimeOrigin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo
+			+ From<frame_system::Call<Self>>;
+
+		/// The preimage provider used to look up the attached calls.
+		type Preimages: QueryPreimage + StorePreimage;
+
 		/// Type to access the Balances Pallet.
 		type Currency: Currency<Self::AccountId>
-			+ ReservableCurrency<Self::AccountId>
+			+ NamedReservableCurrency<Self::AccountId>
 			+ LockableCurrency<Self::AccountId>;
 
+		/// Identifier of the named reserve of the stake that gives the votes.
+		type VotingReserveId: Get<ReserveIdOf<Self>>;
+
+		/// Identifier of the named reserve of the register fees paid before the fees funded the
+		/// reward pot, which are refunded when the voter withdraws.
+		type RegisterReserveId: Get<ReserveIdOf<Self>>;
+
+		/// Identifier of the named reserve of the deposits of the queued proposals.
+		type DepositReserveId: Get<ReserveIdOf<Self>>;
+
+		/// Identifier of the named reserve of the unbonded stake waiting to be withdrawn.
+		type UnbondingReserveId: Get<ReserveIdOf<Self>>;
+
+		/// Fee paid to the reward pot to register as a voter.
 		#[pallet::constant]
 		type RegisterFee: Get<BalanceOf<Self>>;
 
+		/// Mapping of the reserved tokens to votes.
+		type VotePowerCurve: VotePowerCurve;
+
 		#[pallet::constant]
 		type MaxVecLen: Get<u32>;
 
+		/// Minimum number of blocks that a proposal accepts votes.
+		#[pallet::constant]
+		type MinProposalDuration: Get<Self::BlockNumber>;
+
+		/// Maximum number of blocks that a proposal accepts votes, also the duration of the
+		/// proposals created without one.
 		#[pallet::constant]
 		type MaxProposalDuration: Get<Self::BlockNumber>;
 
+		/// Maximum number of blocks between the creation of a proposal and its start block.
+		#[pallet::constant]
+		type MaxStartDelay: Get<Self::BlockNumber>;
+
+		/// Maximum number of proposals that can be open at the same time.
+		#[pallet::constant]
+		type MaxActiveProposals: Get<u32>;
+
+		/// Maximum number of expired proposals finalized in `on_initialize` every block.
+		#[pallet::constant]
+		type MaxFinalizationsPerBlock: Get<u32>;
+
+		/// Maximum number of ballot entries of the closed proposals removed in `on_idle` every
+		/// block.
+		#[pallet::constant]
+		type MaxBallotRemovalsPerBlock: Get<u32>;
+
+		/// Maximum number of distinct rankings stored for a ranked-choice proposal.
+		#[pallet::constant]
+		type MaxRankings: Get<u32>;
+
+		/// Number of blocks after the end block of a secret proposal to reveal the ballots.
+		#[pallet::constant]
+		type RevealPeriod: Get<Self::BlockNumber>;
+
+		/// Part of the stake slashed from a voter that does not reveal a committed ballot.
+		#[pallet::constant]
+		type UnrevealedSlash: Get<Perbill>;
+
+		/// Base period that the stake backing a conviction vote stays locked after the proposal ends.
+		#[pallet::constant]
+		type VoteLockingPeriod: Get<Self::BlockNumber>;
+
+		/// Source of randomness used to break the ties with the `Random` policy.
+		type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
+
+		/// Origin that picks the winner of the ties with the `Origin` policy.
+		type TieBreakOrigin: EnsureOrigin<<Self as frame_system::Config>::RuntimeOrigin>;
+
+		/// Origin that cancels an active proposal.
+		type CancelOrigin: EnsureOrigin<<Self as frame_system::Config>::RuntimeOrigin>;
+
+		/// Origin that vetoes a proposal text, blocking it from being proposed again for the
+		/// `CooldownPeriod`.
+		type VetoOrigin: EnsureOrigin<<Self as frame_system::Config>::RuntimeOrigin>;
+
+		/// Blocks that a vetoed proposal text cannot be proposed.
+		#[pallet::constant]
+		type CooldownPeriod: Get<Self::BlockNumber>;
+
+		/// Tie-break policy of the proposals created without one.
+		#[pallet::constant]
+		type DefaultTieBreak: Get<TieBreak>;
+
+		/// Number of blocks between the end of a proposal and the dispatch of the call attached to
+		/// the winner option. The call is dispatched at least one block after the end.
+		#[pallet::constant]
+		type EnactmentDelay: Get<Self::BlockNumber>;
+
+		/// Maximum weight of the calls dispatched in the same block. The calls that do not fit are
+		/// moved to the next block, a call heavier than the limit fails.
+		#[pallet::constant]
+		type MaxEnactmentWeight: Get<Weight>;
+
+		/// Maximum number of hops in a chain of delegations.
+		#[pallet::constant]
+		type MaxDelegationDepth: Get<u32>;
+
+		/// Maximum number of voters that can delegate directly to the same voter.
+		#[pallet::constant]
+		type MaxDelegators: Get<u32>;
+
+		/// Maximum number of voters that can delegate to the same voter directly or through a chain
+		/// of delegations, which bounds the delegators walked when the voter votes.
+		#[pallet::constant]
+		type MaxTotalDelegators: Get<u32>;
+
+		/// Tokens reserved from a voter to submit or second a proposal, refunded when the proposal
+		/// is launched.
+		#[pallet::constant]
+		type ProposalDeposit: Get<BalanceOf<Self>>;
+
+		/// Maximum number of submitted proposals waiting to be launched.
+		#[pallet::constant]
+		type MaxQueuedProposals: Get<u32>;
+
+		/// Every `LaunchPeriod` blocks the most seconded queued proposal is launched. It must not
+		/// be zero.
+		#[pallet::constant]
+		type LaunchPeriod: Get<Self::BlockNumber>;
+
+		/// Maximum number of seconds of a queued proposal.
+		#[pallet::constant]
+		type MaxSeconds: Get<u32>;
+
+		/// Blocks that the unbonded stake waits before it can be withdrawn.
+		#[pallet::constant]
+		type UnbondingPeriod: Get<Self::BlockNumber>;
+
+		/// Maximum number of unbonded amounts of a voter waiting to be withdrawn.
+		#[pallet::constant]
+		type MaxUnlockingChunks: Get<u32>;
+
+		/// Identifier of the account of the reward pot, funded by the top-ups and by the slashes
+		/// routed to it with `RewardPot`.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Reward shared by the voters of every finished proposal, in proportion to the votes they
+		/// spent in it.
+		#[pallet::constant]
+		type ProposalReward: Get<BalanceOf<Self>>;
+
+		/// Number of consecutive finished proposals a voter can skip before being inactive.
+		#[pallet::constant]
+		type MaxMissedProposals: Get<u32>;
+
+		/// Part of the register fee slashed from the stake of an inactive voter when it is reaped.
+		#[pallet::constant]
+		type InactivitySlash: Get<Perbill>;
+
+		/// Handler of the stakes slashed from the inactive voters and from the unrevealed ballots,
+		/// `RewardPot` pays them to the reward pot.
+		type InactivitySlashHandler: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Fungible assets held by the voters to get votes in the proposals that name an asset.
+		/// The runtimes without assets can use `assets::NoAssets`.
+		type Assets: fungibles::Inspect<Self::AccountId> + fungibles::MutateHold<Self::AccountId>;
+
+		/// Creates a funded asset for the benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type AssetBenchmarkHelper: crate::assets::BenchmarkHelper<
+			AssetIdOf<Self>,
+			Self::AccountId,
+			AssetBalanceOf<Self>,
+		>;
+
 		type WeightInfo: weights::WeightInfo;
 	}
 
 
 	#[derive( Clone, Encode, Decode, TypeInfo,MaxEncodedLen, Debug, Copy, Eq, PartialEq)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 	#[scale_info(skip_type_params(T))]
 	pub struct Options<Hash>{
 		pub id: u8,
@@ -69,37 +293,255 @@ pub mod pallet {
 	}
 
 	#[derive( Clone, Encode, Decode, TypeInfo,MaxEncodedLen, Debug, Copy, Eq, PartialEq)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 	pub struct VoteStruct {
 		pub id: u8,
 		pub votes: u128,
 	}
 
+	/// A ranking of option ids, from the most to the least preferred, and the votes backing it.
+	#[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Clone, Debug, Eq, PartialEq)]
+	#[scale_info(skip_type_params(T))]
+	pub struct RankedBallot<T: Config> {
+		pub ranking: BoundedVec<u8, T::MaxVecLen>,
+		pub votes: u128,
+	}
+
+	/// The tallies of one instant-runoff or single transferable vote round, the options elected
+	/// in it and the option eliminated after it, if any.
+	#[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Clone, Debug, Eq, PartialEq)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	#[cfg_attr(feature = "std", serde(bound = ""))]
+	#[scale_info(skip_type_params(T))]
+	pub struct RunoffRound<T: Config> {
+		pub tallies: BoundedVec<VoteStruct, T::MaxVecLen>,
+		pub elected: BoundedVec<u8, T::MaxVecLen>,
+		pub eliminated: Option<u8>,
+	}
+
 	#[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Clone, Debug, Eq, PartialEq)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	#[cfg_attr(feature = "std", serde(bound = ""))]
 	#[scale_info(skip_type_params(T))]
 	pub struct FinishedProposal<T: Config> {
 		pub id: u32,
 		pub text: T::Hash,
 		pub end_block: T::BlockNumber,
 		pub status: ProposalStatus,
+		pub ballot_type: BallotType,
 		pub options_votes: BoundedVec<Options<T::Hash>, T::MaxVecLen>,
 		pub winner_index: u8,
+		/// Instant-runoff or single transferable vote rounds, empty for the other ballot types.
+		pub rounds: BoundedVec<RunoffRound<T>, T::MaxVecLen>,
+		pub tie_break: TieBreak,
+		/// The options tied for the lead and their votes, empty when there is no tie.
+		pub tied: BoundedVec<VoteStruct, T::MaxVecLen>,
+		/// Schulze ranking of the options, from the winner to the last one, empty for the other
+		/// ballot types.
+		pub ranking: BoundedVec<u8, T::MaxVecLen>,
+		/// Pairwise preferences of a condorcet proposal, see `PairwiseMatrices`.
+		pub pairwise: BoundedVec<u128, MaxPairwiseEntries<T>>,
+		/// Options elected in a single transferable vote proposal in election order, empty for the
+		/// other ballot types.
+		pub winners: BoundedVec<u8, T::MaxVecLen>,
+	}
+
+	/// A ballot cast in a proposal.
+	#[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Clone, Debug, Eq, PartialEq)]
+	#[scale_info(skip_type_params(T))]
+	pub enum Ballot<T: Config> {
+		Split(BoundedVec<VoteStruct, T::MaxVecLen>),
+		Ranked(BoundedVec<u8, T::MaxVecLen>),
+		/// Approval or score ballot, with the score given to every option.
+		Scored(BoundedVec<VoteStruct, T::MaxVecLen>),
+	}
+
+	/// The ballot of a voter and the votes, own and delegated, that were available to cast it.
+	#[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Clone, Debug, Eq, PartialEq)]
+	#[scale_info(skip_type_params(T))]
+	pub struct CastVote<T: Config> {
+		pub ballot: Ballot<T>,
+		pub power: u128,
 	}
 
 	#[derive( Encode, Decode, TypeInfo, MaxEncodedLen, Eq, PartialEq, Clone, Debug)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	#[cfg_attr(feature = "std", serde(bound = ""))]
 	#[scale_info(skip_type_params(T))]
 	pub struct CurrentProposal <T: Config>{
 		pub id: u32,
+		/// The voter power in the proposal is the power they had at the end of this block, the
+		/// block before the proposal was created.
+		pub snapshot_block: T::BlockNumber,
+		/// First block that the proposal accepts votes.
+		pub start_block: T::BlockNumber,
 		pub end_block: T::BlockNumber,
+		/// Last block to reveal the committed ballots, only set for secret proposals.
+		pub reveal_end_block: Option<T::BlockNumber>,
 		pub status: ProposalStatus,
 		pub text: T::Hash,
+		pub ballot_type: BallotType,
 		pub options: BoundedVec<Options<T::Hash>, T::MaxVecLen>,
+		pub thresholds: Thresholds,
+		pub tie_break: TieBreak,
+		/// Number of ballots counted in the tallies.
+		pub voters: u32,
+		/// Votes spent by the voters, the base of the participation rewards.
+		pub spent_votes: u128,
 	} 
 	
+	/// A proposal submitted by a voter, waiting in the queue to be launched.
+	#[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Clone, Debug, Eq, PartialEq)]
+	#[scale_info(skip_type_params(T))]
+	pub struct PendingProposal<T: Config> {
+		pub index: u32,
+		pub proposer: T::AccountId,
+		pub deposit: BalanceOf<T>,
+		pub text: T::Hash,
+		pub options: BoundedVec<Options<T::Hash>, T::MaxVecLen>,
+		pub ballot_type: BallotType,
+		pub secret: bool,
+		pub thresholds: Thresholds,
+		pub tie_break: TieBreak,
+		pub calls: BoundedVec<(u8, BoundedCallOf<T>), T::MaxVecLen>,
+		/// Voters backing the proposal, each one with a deposit reserved.
+		pub seconds: BoundedVec<T::AccountId, T::MaxSeconds>,
+	}
+
+	/// How the voters fill their ballots and how the proposal is tallied.
+	#[derive(Debug, Clone, Encode, Decode, TypeInfo, MaxEncodedLen, Eq, PartialEq, Copy)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub enum BallotType {
+		/// The voters split their votes between the options, the most voted option wins.
+		Split,
+		/// The voters rank the options, the winner is selected by instant-runoff.
+		RankedChoice,
+		/// The voters rank the options, the winner is selected by the Schulze method over the
+		/// pairwise preferences of the ballots.
+		Condorcet,
+		/// The voters approve any subset of the options, every approved option gets all the votes
+		/// of the voter. The most approved option wins.
+		Approval,
+		/// The voters give every option a score from 0 to the given maximum, every option gets its
+		/// score times the votes of the voter. The option with the highest total wins.
+		Score(u8),
+		/// The voters rank the options and the given number of seats is filled by single
+		/// transferable vote, with the Droop quota and fractional surplus transfers.
+		SingleTransferable(u8),
+	}
+
+	/// Conditions that a proposal must meet to accept its winner option.
+	#[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, Debug, Copy, Eq, PartialEq, Default)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub struct Thresholds {
+		/// Minimum votes counted in the proposal. A proposal without votes never reaches the quorum.
+		pub min_votes: u128,
+		/// Minimum number of ballots counted in the proposal.
+		pub min_voters: u32,
+		/// Minimum share of the counted votes that the winner option needs, if any.
+		pub approval: Option<Perbill>,
+	}
+
+	/// Multiplier of the own votes of a ballot, in exchange for locking the stake of the voter
+	/// after the proposal ends.
+	#[derive(Debug, Clone, Encode, Decode, TypeInfo, MaxEncodedLen, Eq, PartialEq, Copy)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub enum Conviction {
+		/// 1x votes, no lock.
+		None,
+		/// 2x votes, locked for 1 `VoteLockingPeriod`.
+		Locked2x,
+		/// 3x votes, locked for 2 `VoteLockingPeriod`.
+		Locked3x,
+		/// 4x votes, locked for 4 `VoteLockingPeriod`.
+		Locked4x,
+		/// 5x votes, locked for 8 `VoteLockingPeriod`.
+		Locked5x,
+		/// 6x votes, locked for 16 `VoteLockingPeriod`.
+		Locked6x,
+	}
+
+	impl Default for Conviction {
+		fn default() -> Self {
+			Conviction::None
+		}
+	}
+
+	impl Conviction {
+		/// Multiplier of the own votes of the voter.
+		pub fn multiplier(self) -> u128 {
+			match self {
+				Conviction::None => 1,
+				Conviction::Locked2x => 2,
+				Conviction::Locked3x => 3,
+				Conviction::Locked4x => 4,
+				Conviction::Locked5x => 5,
+				Conviction::Locked6x => 6,
+			}
+		}
+
+		/// Number of `VoteLockingPeriod` that the stake stays locked after the proposal ends.
+		pub fn lock_periods(self) -> u32 {
+			match self {
+				Conviction::None => 0,
+				Conviction::Locked2x => 1,
+				Conviction::Locked3x => 2,
+				Conviction::Locked4x => 4,
+				Conviction::Locked5x => 8,
+				Conviction::Locked6x => 16,
+			}
+		}
+	}
+
+	/// How a tie between the most voted options is resolved.
+	#[derive(Debug, Clone, Encode, Decode, TypeInfo, MaxEncodedLen, Eq, PartialEq, Copy)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub enum TieBreak {
+		/// The proposal is closed as tied, without a winner.
+		Declare,
+		/// A new proposal is opened among the tied options. If it ties again the tie is declared.
+		Runoff,
+		/// The winner is picked among the tied options with on-chain randomness.
+		Random,
+		/// The `TieBreakOrigin` picks the winner among the tied options.
+		Origin,
+	}
+
+	/// The condition that a proposal did not meet.
+	#[derive(Debug, Clone, Encode, Decode, TypeInfo, MaxEncodedLen, Eq, PartialEq, Copy)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub enum RejectionReason {
+		/// Less votes than `min_votes`.
+		NotEnoughVotes,
+		/// Less ballots than `min_voters`.
+		NotEnoughVoters,
+		/// The winner option got less than the `approval` share of the votes.
+		NotEnoughApproval,
+	}
+
+	/// Origin of the calls dispatched by the winner options.
+	#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+	pub enum RawOrigin {
+		/// The winner option of the proposal with the given id.
+		Proposal(u32),
+	}
+
+	#[pallet::origin]
+	pub type Origin = RawOrigin;
 
 	#[derive(Debug, Clone, Encode, Decode, TypeInfo, MaxEncodedLen, Eq, PartialEq, Copy)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 	pub enum ProposalStatus {
 		InProgress,
 		Finished,
+		/// The winner option did not reach the approval threshold.
+		Rejected,
+		/// The proposal did not reach the minimum votes or voters.
+		NoQuorum,
+		/// The most voted options are tied and no winner was picked.
+		Tied,
+		/// The proposal was cancelled by the `CancelOrigin` and its tallies discarded.
+		Cancelled,
 	}
 
 	// The pallet's runtime storage items.
@@ -110,9 +552,58 @@ pub mod pallet {
 	// https://docs.substrate.io/main-docs/build/runtime-storage/#declaring-storage-items
 	/// Storage item to store accounts that can vote.
 	pub type Voters<T: Config> = StorageMap<_, Blake2_128, T::AccountId, u128>;
+
+	/// Bound of the vote power checkpoints of a voter: one for the snapshot of every open and
+	/// recent proposal, the power before the current block and the current power.
+	pub struct MaxPowerCheckpoints<T>(PhantomData<T>);
+
+	impl<T: Config> Get<u32> for MaxPowerCheckpoints<T> {
+		fn get() -> u32 {
+			T::MaxActiveProposals::get()
+				.saturating_add(MaxRecentProposals::<T>::get())
+				.saturating_add(2)
+		}
+	}
+
+	/// Bound of the pairwise preference matrix of a condorcet proposal, one entry for every
+	/// ordered pair of options.
+	pub struct MaxPairwiseEntries<T>(PhantomData<T>);
+
+	impl<T: Config> Get<u32> for MaxPairwiseEntries<T> {
+		fn get() -> u32 {
+			T::MaxVecLen::get().saturating_mul(T::MaxVecLen::get())
+		}
+	}
+
+	/// Bound of the latest finished proposals checked for the activity of the voters, one more
+	/// than the proposals a voter can skip.
+	pub struct MaxRecentProposals<T>(PhantomData<T>);
+
+	impl<T: Config> Get<u32> for MaxRecentProposals<T> {
+		fn get() -> u32 {
+			T::MaxMissedProposals::get().saturating_add(1)
+		}
+	}
+
+	/// Vote power of the voters since the given block, used to get their power at the snapshot
+	/// block of the open proposals.
+	#[pallet::storage]
+	pub type PowerCheckpoints<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<(T::BlockNumber, u128), MaxPowerCheckpoints<T>>,
+		ValueQuery,
+	>;
 	
-	 #[pallet::storage]
-	pub type ActiveProposal<T: Config> = StorageValue<_, CurrentProposal<T>>;
+	/// Storage item to store the open proposals by id.
+	#[pallet::storage]
+	pub type ActiveProposals<T: Config> = StorageMap<_, Blake2_128, u32, CurrentProposal<T>>;
+
+	/// Ids of the open proposals, bounded by `MaxActiveProposals`.
+	#[pallet::storage]
+	pub type ActiveProposalIds<T: Config> =
+		StorageValue<_, BoundedVec<u32, T::MaxActiveProposals>, ValueQuery>;
 
 	#[pallet::type_value]
 	pub fn DefaultProposalCounter<T: Config>() -> u32 { 1u32 }
@@ -120,11 +611,152 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type ProposalCount<T: Config> = StorageValue<_, u32, ValueQuery, DefaultProposalCounter<T>>;
 	
+	/// Proposals submitted by the voters in submission order, bounded by `MaxQueuedProposals`.
+	#[pallet::storage]
+	pub type ProposalQueue<T: Config> =
+		StorageValue<_, BoundedVec<PendingProposal<T>, T::MaxQueuedProposals>, ValueQuery>;
+
+	/// Number of proposals submitted by the voters, used as the index of the next one.
+	#[pallet::storage]
+	pub type SubmittedProposalCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Vetoed proposal texts and the last block of their cooldown.
+	#[pallet::storage]
+	pub type Blacklist<T: Config> = StorageMap<_, Identity, T::Hash, T::BlockNumber>;
+
 	#[pallet::storage]
 	pub type FinishedProposals<T: Config> = StorageMap<_, Blake2_128, u32 ,FinishedProposal<T>>;
 
+	/// Distinct rankings submitted to a ranked-choice proposal.
+	#[pallet::storage]
+	pub type RankedBallots<T: Config> =
+		StorageMap<_, Blake2_128, u32, BoundedVec<RankedBallot<T>, T::MaxRankings>, ValueQuery>;
+
+	/// Pairwise preferences of the open condorcet proposals. The entry `i * n + j`, with `n`
+	/// options, holds the votes that prefer the option at position `i` over the one at `j`.
+	#[pallet::storage]
+	pub type PairwiseMatrices<T: Config> =
+		StorageMap<_, Blake2_128, u32, BoundedVec<u128, MaxPairwiseEntries<T>>, ValueQuery>;
+
+	/// Ballot commitments of the secret proposals that are not revealed yet.
+	#[pallet::storage]
+	pub type Commitments<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, u32, T::Hash>;
+
+	/// Votes spent by an account in a proposal.
+	#[pallet::storage]
+	pub type VotedProposals<T: Config> =
+		StorageDoubleMap<_, Blake2_128, u32, Blake2_128Concat, T::AccountId, u128>;
+
+	/// Calls attached to the options of the open proposals.
+	#[pallet::storage]
+	pub type OptionCalls<T: Config> =
+		StorageMap<_, Blake2_128, u32, BoundedVec<(u8, BoundedCallOf<T>), T::MaxVecLen>, ValueQuery>;
+
+	/// Calls of the winner options waiting to be dispatched, by enactment block.
+	#[pallet::storage]
+	pub type Enactments<T: Config> = StorageMap<
+		_,
+		Blake2_128,
+		T::BlockNumber,
+		BoundedVec<(u32, BoundedCallOf<T>), T::MaxActiveProposals>,
+		ValueQuery,
+	>;
+
+	/// Stake of the voters locked by conviction votes and the block when the lock expires.
+	#[pallet::storage]
+	pub type VoteLocks<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (BalanceOf<T>, T::BlockNumber)>;
+
+	/// Stake unbonded by the voters and the block from which each amount can be withdrawn.
 	#[pallet::storage]
-	pub type VotedProposals<T: Config> = StorageMap<_, Blake2_128, T::AccountId, u32, ValueQuery>;
+	pub type Unlocking<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<(BalanceOf<T>, T::BlockNumber), T::MaxUnlockingChunks>,
+		ValueQuery,
+	>;
+
+	/// Reward of the finished proposals and the votes spent in them, claimed by the voters with
+	/// `claim_rewards`.
+	#[pallet::storage]
+	pub type ProposalRewards<T: Config> = StorageMap<_, Blake2_128, u32, (BalanceOf<T>, u128)>;
+
+	/// Voters that already claimed the reward of a proposal.
+	#[pallet::storage]
+	pub type ClaimedRewards<T: Config> =
+		StorageDoubleMap<_, Blake2_128, u32, Blake2_128Concat, T::AccountId, ()>;
+
+	/// Id of the first proposal created after each voter registered. The proposals created
+	/// before are not counted as missed.
+	#[pallet::storage]
+	pub type VoterSince<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Asset that gives the vote power of a proposal, kept once the proposal is finished to slash
+	/// its unrevealed ballots. The proposals without an asset use the stake of the native currency.
+	#[pallet::storage]
+	pub type ProposalAssets<T: Config> = StorageMap<_, Blake2_128, u32, AssetIdOf<T>>;
+
+	/// Tokens of an asset held by the voters to vote in the proposals of the asset.
+	#[pallet::storage]
+	pub type AssetStakes<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		AssetIdOf<T>,
+		AssetBalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// Vote power given by the tokens of an asset held by the voters since the given block, used
+	/// to get their power at the snapshot block of the open proposals of the asset.
+	#[pallet::storage]
+	pub type AssetPowerCheckpoints<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		AssetIdOf<T>,
+		BoundedVec<(T::BlockNumber, u128), MaxPowerCheckpoints<T>>,
+		ValueQuery,
+	>;
+
+	/// Ids and snapshot blocks of the latest finished proposals, the oldest first.
+	#[pallet::storage]
+	pub type RecentProposals<T: Config> =
+		StorageValue<_, BoundedVec<(u32, T::BlockNumber), MaxRecentProposals<T>>, ValueQuery>;
+
+	/// Ballots cast in the open proposals, cleared when the proposal is closed.
+	#[pallet::storage]
+	pub type CastVotes<T: Config> =
+		StorageDoubleMap<_, Blake2_128, u32, Blake2_128, T::AccountId, CastVote<T>>;
+
+	/// Voter to whom an account delegates its votes.
+	#[pallet::storage]
+	pub type Delegations<T: Config> = StorageMap<_, Blake2_128, T::AccountId, T::AccountId>;
+
+	/// Voters that delegate directly to an account.
+	#[pallet::storage]
+	pub type Delegators<T: Config> =
+		StorageMap<_, Blake2_128, T::AccountId, BoundedVec<T::AccountId, T::MaxDelegators>, ValueQuery>;
+
+	/// Number of voters that delegate to an account directly or through a chain of delegations,
+	/// bounded by `MaxTotalDelegators`.
+	#[pallet::storage]
+	pub type DelegatorCount<T: Config> = StorageMap<_, Blake2_128, T::AccountId, u32, ValueQuery>;
+
+	/// Own votes of a delegator counted in the ballot that a delegate up its chain cast in a
+	/// proposal. Only these votes are taken back from the ballot.
+	#[pallet::storage]
+	pub type DelegatedVotes<T: Config> =
+		StorageDoubleMap<_, Blake2_128, u32, Blake2_128, T::AccountId, u128, ValueQuery>;
+
+	/// Closed proposals whose ballots are still stored, removed in bounded batches by `on_idle`.
+	/// The cancelled ones lose their commitments and `VotedProposals` entries too.
+	#[pallet::storage]
+	pub type ClosedBallots<T: Config> = StorageMap<_, Blake2_128Concat, u32, bool>;
 
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/main-docs/build/events-errors/
@@ -134,15 +766,67 @@ pub mod pallet {
 		/// New voter added.
 		NewVoter { who: T::AccountId },
 		/// Votes Emited
-		VotesEmited { who: T::AccountId, votes: u128 },
+		VotesEmited { who: T::AccountId, votes: u128, curve: Curve },
 		/// Proposal Created
 		ProposalCreated { id: u32 },
 		/// Vote Casted
 		VotesDeposited { who: T::AccountId, proposal_id: u32, votes: BoundedVec<VoteStruct, T::MaxVecLen> },
+		/// Ranked vote casted
+		RankedVoteDeposited { who: T::AccountId, proposal_id: u32, ranking: BoundedVec<u8, T::MaxVecLen>, votes: u128 },
+		/// Secret vote committed
+		VoteCommitted { who: T::AccountId, proposal_id: u32 },
+		/// Unrevealed vote slashed
+		UnrevealedVoteSlashed { who: T::AccountId, proposal_id: u32, amount: BalanceOf<T> },
+		/// Unrevealed vote of an asset proposal slashed from the held tokens of the asset
+		UnrevealedAssetSlashed { who: T::AccountId, proposal_id: u32, asset: AssetIdOf<T>, amount: AssetBalanceOf<T> },
 		/// Proposal Finished
 		ProposalFinished { id: u32, winner_index: u8, winner_votes: u128 },
+		/// Proposal closed without a winner
+		ProposalRejected { id: u32, reason: RejectionReason },
+		/// The most voted options are tied, `runoff` is the proposal opened among them
+		ProposalTied { id: u32, options: BoundedVec<u8, T::MaxVecLen>, runoff: Option<u32> },
+		/// The call of the winner option is scheduled
+		ProposalScheduled { id: u32, when: T::BlockNumber },
+		/// The call of the winner option was dispatched
+		ProposalEnacted { id: u32, result: DispatchResult },
+		/// Ballot retracted from a proposal
+		VoteRetracted { who: T::AccountId, proposal_id: u32 },
 		/// Votes Withdrawn
 		VotesWithdrawn { who: T::AccountId },
+		/// The conviction lock expired and was removed
+		Unlocked { who: T::AccountId },
+		/// Votes delegated
+		Delegated { who: T::AccountId, to: T::AccountId },
+		/// Delegation revoked
+		Undelegated { who: T::AccountId, from: T::AccountId },
+		/// Proposal submitted to the queue
+		ProposalSubmitted { who: T::AccountId, index: u32 },
+		/// Queued proposal seconded
+		Seconded { who: T::AccountId, index: u32 },
+		/// Queued proposal launched as an active proposal
+		ProposalLaunched { index: u32, id: u32 },
+		/// Queued proposal dropped because it could not be launched
+		LaunchFailed { index: u32, error: DispatchError },
+		/// Active proposal cancelled
+		ProposalCancelled { id: u32 },
+		/// Proposal text vetoed until the given block
+		ProposalVetoed { text: T::Hash, until: T::BlockNumber },
+		/// Queued proposal cancelled as spam and its deposit slashed
+		ProposalSlashed { index: u32, who: T::AccountId, amount: BalanceOf<T> },
+		/// Stake unbonded, it can be withdrawn from the given block
+		Unbonded { who: T::AccountId, amount: BalanceOf<T>, until: T::BlockNumber },
+		/// Unbonded stake released
+		UnbondedWithdrawn { who: T::AccountId, amount: BalanceOf<T> },
+		/// Tokens added to the reward pot
+		RewardsToppedUp { amount: BalanceOf<T> },
+		/// Reward of a proposal paid to a voter
+		RewardClaimed { who: T::AccountId, proposal_id: u32, amount: BalanceOf<T> },
+		/// Inactive voter removed and part of the register fee slashed from its stake
+		InactiveVoterReaped { who: T::AccountId, slashed: BalanceOf<T> },
+		/// Tokens of an asset held to vote in the proposals of the asset
+		AssetHeld { who: T::AccountId, asset: AssetIdOf<T>, amount: AssetBalanceOf<T> },
+		/// Tokens of an asset released
+		AssetReleased { who: T::AccountId, asset: AssetIdOf<T>, amount: AssetBalanceOf<T> },
 	}
 
 	// Errors inform users that something went wrong.
@@ -154,9 +838,9 @@ pub mod pallet {
 		NotAVoter,
 		/// Invalid amount of reserved tokens
 		InvalidTokenAmount,
-		/// A proposal is already active
-		ProposalAlreadyActive,
-		/// There is no active proposal
+		/// The maximum number of active proposals has been reached
+		TooManyActiveProposals,
+		/// There is no active proposal with the given id
 		NoActiveProposal,
 		/// Not enough votes
 		NotEnoughVotes,
@@ -168,6 +852,114 @@ pub mod pallet {
 		InvalidOptionId,
 		/// Proposal finished
 		ProposalFinished,
+		/// The voter has votes locked in an open proposal
+		VotesLocked,
+		/// The ballot does not match the ballot type of the proposal
+		InvalidBallotType,
+		/// The ranking is empty or repeats an option
+		InvalidRanking,
+		/// The proposal cannot store more distinct rankings
+		TooManyRankings,
+		/// The proposal is secret, the ballots must be committed and revealed
+		SecretBallotRequired,
+		/// The proposal is not secret
+		NotASecretProposal,
+		/// The proposal is not in its reveal phase
+		NotInRevealPhase,
+		/// There is no committed ballot to reveal
+		NoCommitment,
+		/// The revealed ballot does not match the commitment
+		InvalidReveal,
+		/// A voter cannot delegate to itself
+		SelfDelegation,
+		/// The account is already delegating its votes
+		AlreadyDelegating,
+		/// The account is not delegating its votes
+		NotDelegating,
+		/// The delegation would create a cycle
+		DelegationCycle,
+		/// The delegation chain would be longer than `MaxDelegationDepth`
+		DelegationTooDeep,
+		/// The delegate has reached `MaxDelegators`, or a voter up its chain `MaxTotalDelegators`
+		TooManyDelegators,
+		/// The proposal is not waiting for the origin to break a tie
+		NotTied,
+		/// The voter has no ballot to change in the proposal
+		NotVoted,
+		/// The account has no conviction lock
+		NoVoteLock,
+		/// The conviction lock has not expired yet
+		VoteLockNotExpired,
+		/// The proposal queue is full
+		TooManyQueuedProposals,
+		/// There is no queued proposal with this index
+		ProposalNotQueued,
+		/// The queued proposal has the maximum number of seconds
+		TooManySeconds,
+		/// The proposal text is vetoed
+		ProposalVetoed,
+		/// The proposal was cancelled
+		ProposalCancelled,
+		/// The proposal does not accept votes yet
+		ProposalNotStarted,
+		/// The start block of the proposal is in the past or more than `MaxStartDelay` blocks ahead
+		InvalidStartBlock,
+		/// The duration of the proposal is out of the `MinProposalDuration` and `MaxProposalDuration` bounds
+		InvalidDuration,
+		/// The score is above the maximum score of the proposal
+		InvalidScore,
+		/// The seat count is zero or not lower than the number of options
+		InvalidSeats,
+		/// The options of a multi-winner proposal cannot have calls
+		CallsNotAllowed,
+		/// The voter has the maximum number of unbonded amounts waiting
+		TooManyUnlockingChunks,
+		/// There is no unbonded stake ready to be withdrawn
+		NothingToWithdraw,
+		/// The proposal has no reward to claim
+		NoReward,
+		/// The voter already claimed the reward of the proposal
+		RewardAlreadyClaimed,
+		/// The voter did not skip enough consecutive proposals to be reaped
+		VoterNotInactive,
+		/// The asset does not exist
+		UnknownAsset,
+		/// The voter holds no tokens of the asset
+		NoAssetStake,
+		/// The ballots of the asset proposals cannot be cast with conviction
+		ConvictionNotAllowed,
+		/// The call of the winner option weighs more than `MaxEnactmentWeight`
+		EnactmentTooHeavy,
+		/// The ballot was cast before the ballots were stored and cannot be changed
+		BallotNotStored,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Dispatch the calls of the winner options, finalize the expired proposals, up to
+		/// `MaxFinalizationsPerBlock`, and launch the most seconded queued proposal every
+		/// `LaunchPeriod` blocks.
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			Self::enact_proposals(n)
+				.saturating_add(Self::finalize_expired(n, T::MaxFinalizationsPerBlock::get(), Weight::MAX))
+				.saturating_add(Self::launch_queued(n))
+		}
+
+		/// Check that the launch period can divide the block numbers.
+		fn integrity_test() {
+			assert!(!T::LaunchPeriod::get().is_zero(), "`LaunchPeriod` must not be zero");
+		}
+
+		/// Use the leftover block weight to finalize the expired proposals that did not fit in
+		/// `on_initialize`, and then to remove the ballots of the closed proposals.
+		fn on_idle(n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			let base_weight = T::DbWeight::get().reads(1 + T::MaxActiveProposals::get() as u64);
+			if remaining_weight.any_lt(base_weight) {
+				return Weight::zero();
+			}
+			let weight = Self::finalize_expired(n, u32::MAX, remaining_weight);
+			weight.saturating_add(Self::clear_closed_ballots(remaining_weight.saturating_sub(weight)))
+		}
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -177,13 +969,14 @@ pub mod pallet {
 	impl<T: Config> Pallet<T> {
 		#[pallet::call_index(0)]
 		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::add_voter())]
-		/// Register a new voter. A fee is needed to register.
+		/// Register a new voter. The register fee is paid to the reward pot and is not refunded.
 		pub fn add_voter(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 			ensure!(!<Voters<T>>::contains_key(who.clone()), Error::<T>::AlreadyVoter);
 			let fee = T::RegisterFee::get();
-			T::Currency::reserve(&who, fee)?;
+			T::Currency::transfer(&who, &Self::reward_pot(), fee, ExistenceRequirement::KeepAlive)?;
 			<Voters<T>>::insert(who.clone(), 0);
+			<VoterSince<T>>::insert(who.clone(), <ProposalCount<T>>::get());
 			Self::deposit_event(Event::NewVoter { who });
 			Ok(().into())
 		}
@@ -196,166 +989,649 @@ pub mod pallet {
 			let who = ensure_signed(origin)?;
 
 			ensure!(<Voters<T>>::contains_key(who.clone()), Error::<T>::NotAVoter);
-			ensure!(amount > T::RegisterFee::get(), Error::<T>::InvalidTokenAmount);
+			ensure!(!amount.is_zero(), Error::<T>::InvalidTokenAmount);
 
-			T::Currency::reserve(&who, amount)?;
-			let reserves_to_compute = T::Currency::reserved_balance(&who) - T::RegisterFee::get();
-			// qed
-			let votes: u128 = reserves_to_compute.integer_sqrt().try_into().ok().unwrap();
+			T::Currency::reserve_named(&T::VotingReserveId::get(), &who, amount)?;
+			let votes = Self::compute_votes(&who);
+			Self::checkpoint_power(&who, votes);
 			<Voters<T>>::mutate(who.clone(), | previous_votes| {
 				*previous_votes = Some(votes);
 			});
-			Self::deposit_event(Event::VotesEmited { who, votes });
+			Self::deposit_event(Event::VotesEmited { who, votes, curve: T::VotePowerCurve::CURVE });
 			Ok(().into())
 		}
 
 		#[pallet::call_index(2)]
 		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::set_proposal())]
-		/// Set a new active proposal
-		pub fn set_proposal(origin: OriginFor<T>, text: T::Hash, vote_options: BoundedVec<Options<T::Hash>,T::MaxVecLen>) -> DispatchResultWithPostInfo {
+		/// Set a new active proposal. A call can be attached to each option, the call of the winner
+		/// option is dispatched `EnactmentDelay` blocks after the end of the proposal. The proposal
+		/// accepts votes from `start_block`, by default the current block, during `duration` blocks,
+		/// by default `MaxProposalDuration`. When an `asset` is given the votes come from the tokens
+		/// of the asset held by the voters.
+		pub fn set_proposal(origin: OriginFor<T>, text: T::Hash, vote_options: BoundedVec<Options<T::Hash>,T::MaxVecLen>, ballot_type: BallotType, secret: bool, thresholds: Thresholds, tie_break: Option<TieBreak>, calls: BoundedVec<(u8, BoundedCallOf<T>),T::MaxVecLen>, start_block: Option<T::BlockNumber>, duration: Option<T::BlockNumber>, asset: Option<AssetIdOf<T>>) -> DispatchResultWithPostInfo {
 			ensure_root(origin)?;
-			ensure!(<ActiveProposal<T>>::get().is_none(), Error::<T>::ProposalAlreadyActive);
+			Self::ensure_valid_calls(&vote_options, &calls)?;
 
-			let proposal_count = <ProposalCount<T>>::get();
-			// create the proposal struct
-			let new_proposal = CurrentProposal::<T> {
-				id: proposal_count,
-				end_block: <frame_system::Pallet<T>>::block_number() + T::MaxProposalDuration::get(),
-				status: ProposalStatus::InProgress,
-				text: text,
-				options: vote_options,
-			};
+			let tie_break = tie_break.unwrap_or_else(T::DefaultTieBreak::get);
+			Self::do_set_proposal(text, vote_options, ballot_type, secret, thresholds, tie_break, calls, start_block, duration, asset)?;
+			Ok(().into())
+		}
 
-			// set the proposal as active
-			<ActiveProposal<T>>::set(Some(new_proposal));
 
-			Self::deposit_event(Event::ProposalCreated { id: proposal_count });
+		#[pallet::call_index(3)]
+		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::vote().saturating_add(Pallet::<T>::delegators_weight(9, 2)))]
+		/// Vote for an active proposal
+		pub fn vote(origin: OriginFor<T>, proposal_id: u32, votes: BoundedVec<VoteStruct,T::MaxVecLen>, conviction: Conviction) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let (active_proposal, available_votes) = Self::ensure_can_vote(&who, proposal_id)?;
+			ensure!(active_proposal.reveal_end_block.is_none(), Error::<T>::SecretBallotRequired);
+
+			let power = Self::apply_conviction(&who, &active_proposal, available_votes, conviction)?;
+			let delegated_votes = Self::take_delegated_votes(&who, proposal_id, 0);
+			Self::record_delegated_votes(&who, &active_proposal);
+			Self::do_vote(&who, active_proposal, &votes, power)?;
+			Self::override_delegation(&who, proposal_id, delegated_votes);
+
+			Self::deposit_event(Event::VotesDeposited { who, proposal_id, votes });
 
-			// increase the proposals count
-			<ProposalCount<T>>::mutate(|count| {
-				*count += 1;
-			});
 			Ok(().into())
 		}
 
-		#[pallet::call_index(3)]
-		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::vote())]
-		/// Vote for a proposal
-		pub fn vote(origin: OriginFor<T>, votes: BoundedVec<VoteStruct,T::MaxVecLen>) -> DispatchResultWithPostInfo {
+		#[pallet::call_index(4)]
+		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::end_proposal(T::MaxVecLen::get(), T::MaxRankings::get()))]
+		/// End an active proposal
+		pub fn end_proposal(origin: OriginFor<T>, proposal_id: u32) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let active_proposal = <ActiveProposals<T>>::get(proposal_id).ok_or(Error::<T>::NoActiveProposal)?;
+			if Self::closing_block(&active_proposal) > <frame_system::Pallet<T>>::block_number() {
+				return Err(Error::<T>::ProposalNotFinished.into());
+			}
+
+			let weight = Self::end_proposal_weight(&active_proposal);
+			Self::do_end_proposal(active_proposal);
+
+			Ok(Some(weight).into())
+		}
+
+		#[pallet::call_index(5)]
+		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::withdraw().saturating_add(Pallet::<T>::delegators_weight(3, 1).saturating_mul(T::MaxActiveProposals::get().into())))]
+		/// Withdraw the votes
+		pub fn withdraw(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 			// check if the voter is registered
 			ensure!(<Voters<T>>::contains_key(who.clone()), Error::<T>::NotAVoter);
-			// check if the voter has mroe than 0 votes
-			ensure!(!<Voters<T>>::get(who.clone()).unwrap().is_zero(), Error::<T>::NotEnoughVotes);
-			// check if there is an active proposal
-			ensure!(<ActiveProposal<T>>::get().is_some(), Error::<T>::NoActiveProposal);
-
-			// check if the proposal is open qed
-			if <ActiveProposal<T>>::get().unwrap().end_block < <frame_system::Pallet<T>>::block_number() {
-				// return error
-				return Err(Error::<T>::ProposalFinished.into());
-			}
+			Self::ensure_can_withdraw(&who)?;
+			Self::do_withdraw(&who);
 
-			// get the active proposal qed
-			let mut active_proposal = <ActiveProposal<T>>::get().unwrap();
+			Self::deposit_event(Event::VotesWithdrawn { who: who.clone() });
 
-			// check if the voter has already voted
-			if <VotedProposals<T>>::get(who.clone()) == active_proposal.id {
-				// return error	
-				return Err(Error::<T>::AlreadyVoted.into());
-			}
+			Ok(().into())
+		}
 
-			// set this proposal as voted
-			<VotedProposals<T>>::insert(who.clone(), active_proposal.id);
+		#[pallet::call_index(6)]
+		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::vote_ranked().saturating_add(Pallet::<T>::delegators_weight(9, 2)))]
+		/// Rank the options of an active ranked-choice proposal, all the voter votes back the ranking
+		pub fn vote_ranked(origin: OriginFor<T>, proposal_id: u32, ranking: BoundedVec<u8,T::MaxVecLen>, conviction: Conviction) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let (active_proposal, available_votes) = Self::ensure_can_vote(&who, proposal_id)?;
+			ensure!(active_proposal.reveal_end_block.is_none(), Error::<T>::SecretBallotRequired);
 
-			// counter to check the used votes
-			let mut used_votes = 0u128;
-			
-			// get the available votes qed
-			let available_votes = <Voters<T>>::get(who.clone()).unwrap();
+			let power = Self::apply_conviction(&who, &active_proposal, available_votes, conviction)?;
+			let delegated_votes = Self::take_delegated_votes(&who, proposal_id, 0);
+			Self::record_delegated_votes(&who, &active_proposal);
+			Self::do_vote_ranked(&who, active_proposal, &ranking, power)?;
+			Self::override_delegation(&who, proposal_id, delegated_votes);
 
-			
-			for i in &votes {
-			 	let proposal_voted = i.id;
-				if u32::from(proposal_voted) >= T::MaxVecLen::get() {
-					return Err(Error::<T>::InvalidOptionId.into());
-				}
-				for j in 0..active_proposal.options.len() {
-					if active_proposal.options[j].id == proposal_voted {
-						active_proposal.options[j].votes += i.votes;
-						used_votes += i.votes;
-					}
-				}
-			}
-			
-			Self::deposit_event(Event::VotesDeposited { who: who.clone(),proposal_id: active_proposal.id, votes: votes.clone() });
-			// verify that the user not vote more than he can
-			ensure!(used_votes <= available_votes, Error::<T>::NotEnoughVotes);
-			// update the active proposal
-			<ActiveProposal<T>>::set(Some(active_proposal));
+			Self::deposit_event(Event::RankedVoteDeposited { who, proposal_id, ranking, votes: power });
 
 			Ok(().into())
 		}
 
-		#[pallet::call_index(4)]
-		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::end_proposal())]
-		/// End the active proposal
-		pub fn end_proposal(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
-			ensure_signed(origin)?;
-			ensure!(<ActiveProposal<T>>::get().is_some(), Error::<T>::NoActiveProposal);
+		#[pallet::call_index(7)]
+		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::commit_vote())]
+		/// Commit the hash of a ballot and a salt to a secret proposal. All the voter votes are
+		/// locked in the proposal until the ballot is revealed.
+		pub fn commit_vote(origin: OriginFor<T>, proposal_id: u32, commitment: T::Hash) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let (mut active_proposal, available_votes) = Self::ensure_can_vote(&who, proposal_id)?;
+			ensure!(active_proposal.reveal_end_block.is_some(), Error::<T>::NotASecretProposal);
 
-			// qed
-			let active_proposal = <ActiveProposal<T>>::get().unwrap();
-			if active_proposal.end_block > <frame_system::Pallet<T>>::block_number() {
-				return Err(Error::<T>::ProposalNotFinished.into());
+			<Commitments<T>>::insert(who.clone(), proposal_id, commitment);
+			Self::record_spent_votes(&mut active_proposal, &who, available_votes);
+			<ActiveProposals<T>>::insert(proposal_id, active_proposal);
+
+			Self::deposit_event(Event::VoteCommitted { who, proposal_id });
+
+			Ok(().into())
+		}
+
+		#[pallet::call_index(8)]
+		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::reveal_vote())]
+		/// Reveal a split ballot committed to a secret proposal
+		pub fn reveal_vote(origin: OriginFor<T>, proposal_id: u32, votes: BoundedVec<VoteStruct,T::MaxVecLen>, salt: [u8; 32]) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let (active_proposal, committed_votes) =
+				Self::ensure_can_reveal(&who, proposal_id, T::Hashing::hash_of(&(votes.clone(), salt)))?;
+
+			Self::do_vote(&who, active_proposal, &votes, committed_votes)?;
+			<Commitments<T>>::remove(who.clone(), proposal_id);
+
+			Self::deposit_event(Event::VotesDeposited { who, proposal_id, votes });
+
+			Ok(().into())
+		}
+
+		#[pallet::call_index(9)]
+		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::reveal_ranked())]
+		/// Reveal a ranking committed to a secret proposal
+		pub fn reveal_ranked(origin: OriginFor<T>, proposal_id: u32, ranking: BoundedVec<u8,T::MaxVecLen>, salt: [u8; 32]) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let (active_proposal, committed_votes) =
+				Self::ensure_can_reveal(&who, proposal_id, T::Hashing::hash_of(&(ranking.clone(), salt)))?;
+
+			Self::do_vote_ranked(&who, active_proposal, &ranking, committed_votes)?;
+			<Commitments<T>>::remove(who.clone(), proposal_id);
+
+			Self::deposit_event(Event::RankedVoteDeposited { who, proposal_id, ranking, votes: committed_votes });
+
+			Ok(().into())
+		}
+
+		#[pallet::call_index(10)]
+		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::slash_unrevealed())]
+		/// Slash a voter that committed a ballot to a finished secret proposal and never revealed it.
+		/// The slashed stake goes to the `InactivitySlashHandler`. Any account can call it.
+		pub fn slash_unrevealed(origin: OriginFor<T>, proposal_id: u32, who: T::AccountId) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let finished_proposal = <FinishedProposals<T>>::get(proposal_id).ok_or(Error::<T>::ProposalNotFinished)?;
+			ensure!(finished_proposal.status != ProposalStatus::Cancelled, Error::<T>::ProposalCancelled);
+			ensure!(<Commitments<T>>::contains_key(who.clone(), proposal_id), Error::<T>::NoCommitment);
+
+			<Commitments<T>>::remove(who.clone(), proposal_id);
+			// the unrevealed ballot gets no participation reward
+			<VotedProposals<T>>::insert(proposal_id, who.clone(), 0);
+
+			// the ballots of an asset proposal were backed by the held tokens of the asset, which
+			// are slashed to the reward pot
+			if let Some(asset) = <ProposalAssets<T>>::get(proposal_id) {
+				let stake = <AssetStakes<T>>::get(who.clone(), asset);
+				let to_slash = T::UnrevealedSlash::get().mul_floor(stake);
+				let amount = <T::Assets as fungibles::MutateHold<T::AccountId>>::transfer_held(
+					asset, &who, &Self::reward_pot(), to_slash, true, false,
+				)?;
+				<AssetStakes<T>>::insert(who.clone(), asset, stake.saturating_sub(amount));
+				Self::checkpoint_asset_power(&who, asset, Self::asset_votes(&who, asset));
+				Self::deposit_event(Event::UnrevealedAssetSlashed { who, proposal_id, asset, amount });
+				return Ok(().into());
 			}
 
-			// search the winner option
-			let mut winner_index:u8 = 0;
-			let mut winner_votes:u128 = 0; 
-			for i in active_proposal.options.iter() {
-				if i.votes > winner_votes {
-					winner_index = i.id as u8;
-					winner_votes = i.votes;
-				}
+			let stake = T::Currency::reserved_balance_named(&T::VotingReserveId::get(), &who);
+			let to_slash = T::UnrevealedSlash::get().mul_floor(stake);
+			let (imbalance, not_slashed) = T::Currency::slash_reserved_named(&T::VotingReserveId::get(), &who, to_slash);
+			let amount = to_slash.saturating_sub(not_slashed);
+			T::InactivitySlashHandler::on_unbalanced(imbalance);
+
+			// the voter power is reduced with the slashed stake
+			if <Voters<T>>::contains_key(who.clone()) {
+				let votes = Self::compute_votes(&who);
+				Self::checkpoint_power(&who, votes);
+				<Voters<T>>::insert(who.clone(), votes);
 			}
-			
-			let new_finished_proposal = FinishedProposal::<T> {
-				id: active_proposal.id,
+
+			Self::deposit_event(Event::UnrevealedVoteSlashed { who, proposal_id, amount });
+
+			Ok(().into())
+		}
+
+		#[pallet::call_index(11)]
+		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::delegate().saturating_add(Pallet::<T>::delegators_weight(1, 0)))]
+		/// Delegate the votes to another voter. The delegate votes with its own votes plus all the
+		/// votes delegated to it, unless the delegator votes directly.
+		pub fn delegate(origin: OriginFor<T>, to: T::AccountId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(<Voters<T>>::contains_key(who.clone()), Error::<T>::NotAVoter);
+			ensure!(<Voters<T>>::contains_key(to.clone()), Error::<T>::NotAVoter);
+			ensure!(who != to, Error::<T>::SelfDelegation);
+			ensure!(!<Delegations<T>>::contains_key(who.clone()), Error::<T>::AlreadyDelegating);
+
+			// walk up the chain of the delegate looking for cycles
+			let mut chain = vec![to.clone()];
+			let mut current = <Delegations<T>>::get(to.clone());
+			while let Some(delegate) = current {
+				ensure!(delegate != who, Error::<T>::DelegationCycle);
+				chain.push(delegate.clone());
+				ensure!(chain.len() as u32 <= T::MaxDelegationDepth::get(), Error::<T>::DelegationTooDeep);
+				current = <Delegations<T>>::get(delegate);
+			}
+			ensure!(
+				chain.len() as u32 + Self::delegation_height(&who, 0) <= T::MaxDelegationDepth::get(),
+				Error::<T>::DelegationTooDeep
+			);
+
+			// the voter brings its own delegators to every voter up the chain
+			let added = <DelegatorCount<T>>::get(who.clone()).saturating_add(1);
+			ensure!(
+				chain.iter().all(|d| <DelegatorCount<T>>::get(d).saturating_add(added) <= T::MaxTotalDelegators::get()),
+				Error::<T>::TooManyDelegators
+			);
+
+			<Delegators<T>>::try_mutate(to.clone(), |delegators| delegators.try_push(who.clone()))
+				.map_err(|_| Error::<T>::TooManyDelegators)?;
+			<Delegations<T>>::insert(who.clone(), to.clone());
+			for delegate in chain {
+				<DelegatorCount<T>>::mutate(delegate, |count| *count = count.saturating_add(added));
+			}
+
+			Self::deposit_event(Event::Delegated { who, to });
+
+			Ok(().into())
+		}
+
+		#[pallet::call_index(12)]
+		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::undelegate().saturating_add(Pallet::<T>::delegators_weight(3, 1).saturating_mul(T::MaxActiveProposals::get().into())))]
+		/// Revoke the delegation. The votes are removed from the ballots that the delegates already
+		/// cast in the open proposals.
+		pub fn undelegate(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(<Delegations<T>>::contains_key(who.clone()), Error::<T>::NotDelegating);
+
+			Self::do_undelegate(&who);
+
+			Ok(().into())
+		}
+
+		#[pallet::call_index(13)]
+		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::resolve_tie())]
+		/// Pick the winner of a finished proposal tied with the `Origin` policy, among the tied
+		/// options. The call of the winner option is scheduled.
+		pub fn resolve_tie(origin: OriginFor<T>, proposal_id: u32, option_id: u8) -> DispatchResultWithPostInfo {
+			T::TieBreakOrigin::ensure_origin(origin)?;
+
+			let winner_votes = <FinishedProposals<T>>::try_mutate(proposal_id, |proposal| -> Result<u128, DispatchError> {
+				let proposal = proposal.as_mut().ok_or(Error::<T>::NotTied)?;
+				ensure!(
+					proposal.status == ProposalStatus::Tied && proposal.tie_break == TieBreak::Origin,
+					Error::<T>::NotTied
+				);
+				let winner = proposal.tied.iter().find(|t| t.id == option_id).copied().ok_or(Error::<T>::InvalidOptionId)?;
+				proposal.status = ProposalStatus::Finished;
+				proposal.winner_index = winner.id;
+				Ok(winner.votes)
+			})?;
+
+			Self::deposit_event(Event::ProposalFinished { id: proposal_id, winner_index: option_id, winner_votes });
+			Self::schedule_enactment(proposal_id, option_id, <OptionCalls<T>>::take(proposal_id));
+
+			Ok(().into())
+		}
+
+		#[pallet::call_index(14)]
+		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::unvote())]
+		/// Retract the ballot from an open proposal. The voter can vote again with `revote` or
+		/// `revote_ranked`, the votes removed from a delegate ballot by the retracted vote are not
+		/// given back to the delegate.
+		pub fn unvote(origin: OriginFor<T>, proposal_id: u32) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let mut active_proposal = Self::ensure_can_change_ballot(&who, proposal_id)?;
+
+			ensure!(Self::remove_ballot(&who, &mut active_proposal), Error::<T>::NotVoted);
+			<ActiveProposals<T>>::insert(proposal_id, active_proposal);
+
+			Self::deposit_event(Event::VoteRetracted { who, proposal_id });
+
+			Ok(().into())
+		}
+
+		#[pallet::call_index(15)]
+		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::revote().saturating_add(Pallet::<T>::delegators_weight(9, 2)))]
+		/// Replace the ballot, or the retracted ballot, of an open split proposal.
+		pub fn revote(origin: OriginFor<T>, proposal_id: u32, votes: BoundedVec<VoteStruct,T::MaxVecLen>, conviction: Conviction) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let mut active_proposal = Self::ensure_can_change_ballot(&who, proposal_id)?;
+
+			Self::remove_ballot(&who, &mut active_proposal);
+			let available_votes = Self::available_votes(&who, &active_proposal);
+			ensure!(!available_votes.is_zero(), Error::<T>::NotEnoughVotes);
+			let power = Self::apply_conviction(&who, &active_proposal, available_votes, conviction)?;
+			Self::record_delegated_votes(&who, &active_proposal);
+			Self::do_vote(&who, active_proposal, &votes, power)?;
+
+			Self::deposit_event(Event::VotesDeposited { who, proposal_id, votes });
+
+			Ok(().into())
+		}
+
+		#[pallet::call_index(16)]
+		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::revote_ranked().saturating_add(Pallet::<T>::delegators_weight(9, 2)))]
+		/// Replace the ranking, or the retracted ranking, of an open ranked-choice proposal.
+		pub fn revote_ranked(origin: OriginFor<T>, proposal_id: u32, ranking: BoundedVec<u8,T::MaxVecLen>, conviction: Conviction) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let mut active_proposal = Self::ensure_can_change_ballot(&who, proposal_id)?;
+
+			Self::remove_ballot(&who, &mut active_proposal);
+			let available_votes = Self::available_votes(&who, &active_proposal);
+			ensure!(!available_votes.is_zero(), Error::<T>::NotEnoughVotes);
+			let power = Self::apply_conviction(&who, &active_proposal, available_votes, conviction)?;
+			Self::record_delegated_votes(&who, &active_proposal);
+			Self::do_vote_ranked(&who, active_proposal, &ranking, power)?;
+
+			Self::deposit_event(Event::RankedVoteDeposited { who, proposal_id, ranking, votes: power });
+
+			Ok(().into())
+		}
+
+		#[pallet::call_index(17)]
+		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::unlock())]
+		/// Remove the conviction lock of the account once it expired.
+		pub fn unlock(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let (_, until) = <VoteLocks<T>>::get(who.clone()).ok_or(Error::<T>::NoVoteLock)?;
+			ensure!(until < <frame_system::Pallet<T>>::block_number(), Error::<T>::VoteLockNotExpired);
+
+			T::Currency::remove_lock(VOTING_ID, &who);
+			<VoteLocks<T>>::remove(who.clone());
+
+			Self::deposit_event(Event::Unlocked { who });
+
+			Ok(().into())
+		}
+
+		#[pallet::call_index(18)]
+		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::submit_proposal())]
+		/// Submit a proposal to the queue. `ProposalDeposit` is reserved from the voter and refunded
+		/// when the proposal is launched.
+		pub fn submit_proposal(origin: OriginFor<T>, text: T::Hash, vote_options: BoundedVec<Options<T::Hash>,T::MaxVecLen>, ballot_type: BallotType, secret: bool, thresholds: Thresholds, tie_break: Option<TieBreak>, calls: BoundedVec<(u8, BoundedCallOf<T>),T::MaxVecLen>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(<Voters<T>>::contains_key(who.clone()), Error::<T>::NotAVoter);
+			Self::ensure_valid_calls(&vote_options, &calls)?;
+			Self::ensure_valid_ballot_type(ballot_type, &vote_options, &calls)?;
+			Self::ensure_not_vetoed(&text)?;
+
+			let index = <SubmittedProposalCount<T>>::get();
+			let deposit = T::ProposalDeposit::get();
+			let pending = PendingProposal::<T> {
+				index,
+				proposer: who.clone(),
+				deposit,
+				text,
+				options: vote_options,
+				ballot_type,
+				secret,
+				thresholds,
+				tie_break: tie_break.unwrap_or_else(T::DefaultTieBreak::get),
+				calls,
+				seconds: BoundedVec::default(),
+			};
+			<ProposalQueue<T>>::try_mutate(|queue| queue.try_push(pending))
+				.map_err(|_| Error::<T>::TooManyQueuedProposals)?;
+			T::Currency::reserve_named(&T::DepositReserveId::get(), &who, deposit)?;
+			<SubmittedProposalCount<T>>::put(index.saturating_add(1));
+
+			Self::deposit_event(Event::ProposalSubmitted { who, index });
+
+			Ok(().into())
+		}
+
+		#[pallet::call_index(19)]
+		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::cancel_queued_proposal())]
+		/// Remove a spam proposal from the queue and slash the deposit of the proposer. The seconding
+		/// deposits are refunded.
+		pub fn cancel_queued_proposal(origin: OriginFor<T>, index: u32) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let mut queue = <ProposalQueue<T>>::get();
+			let position = queue.iter().position(|p| p.index == index).ok_or(Error::<T>::ProposalNotQueued)?;
+			let pending = queue.remove(position);
+			<ProposalQueue<T>>::put(queue);
+
+			let (_, not_slashed) = T::Currency::slash_reserved_named(&T::DepositReserveId::get(), &pending.proposer, pending.deposit);
+			let amount = pending.deposit.saturating_sub(not_slashed);
+			// the voters that seconded the proposal get their deposits back
+			for seconder in pending.seconds.iter() {
+				T::Currency::unreserve_named(&T::DepositReserveId::get(), seconder, pending.deposit);
+			}
+
+			Self::deposit_event(Event::ProposalSlashed { index, who: pending.proposer, amount });
+
+			Ok(().into())
+		}
+
+		#[pallet::call_index(20)]
+		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::second())]
+		/// Back a queued proposal, reserving the same deposit as the proposer. The deposit is
+		/// refunded when the proposal is launched.
+		pub fn second(origin: OriginFor<T>, index: u32) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(<Voters<T>>::contains_key(who.clone()), Error::<T>::NotAVoter);
+
+			let deposit = <ProposalQueue<T>>::try_mutate(|queue| -> Result<BalanceOf<T>, DispatchError> {
+				let pending = queue.iter_mut().find(|p| p.index == index).ok_or(Error::<T>::ProposalNotQueued)?;
+				pending.seconds.try_push(who.clone()).map_err(|_| Error::<T>::TooManySeconds)?;
+				Ok(pending.deposit)
+			})?;
+			T::Currency::reserve_named(&T::DepositReserveId::get(), &who, deposit)?;
+
+			Self::deposit_event(Event::Seconded { who, index });
+
+			Ok(().into())
+		}
+
+		#[pallet::call_index(21)]
+		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::cancel_proposal())]
+		/// Cancel an active proposal. The tallies and the ballots are discarded and the reserved
+		/// stakes are not touched, the proposal is stored in the finished proposals with the
+		/// `Cancelled` status.
+		pub fn cancel_proposal(origin: OriginFor<T>, proposal_id: u32) -> DispatchResultWithPostInfo {
+			T::CancelOrigin::ensure_origin(origin)?;
+			let active_proposal = <ActiveProposals<T>>::take(proposal_id).ok_or(Error::<T>::NoActiveProposal)?;
+			<ActiveProposalIds<T>>::mutate(|ids| ids.retain(|id| *id != proposal_id));
+			<OptionCalls<T>>::remove(proposal_id);
+			<ProposalAssets<T>>::remove(proposal_id);
+			<RankedBallots<T>>::remove(proposal_id);
+			<PairwiseMatrices<T>>::remove(proposal_id);
+			// the ballots and the commitments are removed by `on_idle`
+			<ClosedBallots<T>>::insert(proposal_id, true);
+
+			let mut options_votes = active_proposal.options;
+			for option in options_votes.iter_mut() {
+				option.votes = 0;
+			}
+			let cancelled_proposal = FinishedProposal::<T> {
+				id: proposal_id,
 				text: active_proposal.text,
 				end_block: active_proposal.end_block,
-				status: ProposalStatus::Finished,
-				options_votes: active_proposal.options,
-				winner_index: winner_index,
+				status: ProposalStatus::Cancelled,
+				ballot_type: active_proposal.ballot_type,
+				options_votes,
+				winner_index: 0,
+				rounds: BoundedVec::default(),
+				tie_break: active_proposal.tie_break,
+				tied: BoundedVec::default(),
+				ranking: BoundedVec::default(),
+				pairwise: BoundedVec::default(),
+				winners: BoundedVec::default(),
 			};
+			<FinishedProposals<T>>::insert(proposal_id, cancelled_proposal);
 
-			// add the finished proposal to the finished proposals list
-			<FinishedProposals<T>>::insert(active_proposal.id, new_finished_proposal);
-			// kill the current active proposal
-			<ActiveProposal<T>>::kill();
+			Self::deposit_event(Event::ProposalCancelled { id: proposal_id });
 
-			Self::deposit_event(Event::ProposalFinished { id: active_proposal.id, winner_index: winner_index, winner_votes: winner_votes });
+			Ok(().into())
+		}
+
+		#[pallet::call_index(22)]
+		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::veto())]
+		/// Block a proposal text from being proposed again for the `CooldownPeriod`.
+		pub fn veto(origin: OriginFor<T>, text: T::Hash) -> DispatchResultWithPostInfo {
+			T::VetoOrigin::ensure_origin(origin)?;
+			let until = <frame_system::Pallet<T>>::block_number().saturating_add(T::CooldownPeriod::get());
+			<Blacklist<T>>::insert(text, until);
+
+			Self::deposit_event(Event::ProposalVetoed { text, until });
 
 			Ok(().into())
 		}
 
-		#[pallet::call_index(5)]
-		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::withdraw())]
-		/// Withdraw the votes
-		pub fn withdraw(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+		#[pallet::call_index(23)]
+		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::unbond())]
+		/// Unbond `amount` of the stake. The votes of the voter are computed again with the stake
+		/// left and the unbonded amount can be withdrawn after the `UnbondingPeriod`. The stake
+		/// under an unexpired conviction lock cannot be unbonded, nor the stake of a voter with
+		/// unrevealed ballots.
+		pub fn unbond(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			// check if the voter is registered
 			ensure!(<Voters<T>>::contains_key(who.clone()), Error::<T>::NotAVoter);
-			// check if there is an active proposal
-			ensure!(!<ActiveProposal<T>>::get().is_some(), Error::<T>::ProposalAlreadyActive);
+			ensure!(!Self::has_pending_reveal(&who), Error::<T>::VotesLocked);
+			let stake = T::Currency::reserved_balance_named(&T::VotingReserveId::get(), &who);
+			ensure!(!amount.is_zero() && amount <= stake, Error::<T>::InvalidTokenAmount);
+			let now = <frame_system::Pallet<T>>::block_number();
+			if let Some((locked, until)) = <VoteLocks<T>>::get(who.clone()) {
+				ensure!(until < now || stake - amount >= locked, Error::<T>::VoteLockNotExpired);
+			}
 
-			// kill the voters storage
-			<Voters<T>>::remove(who.clone());
+			let until = now.saturating_add(T::UnbondingPeriod::get());
+			<Unlocking<T>>::try_mutate(who.clone(), |chunks| chunks.try_push((amount, until)))
+				.map_err(|_| Error::<T>::TooManyUnlockingChunks)?;
+			T::Currency::unreserve_named(&T::VotingReserveId::get(), &who, amount);
+			T::Currency::reserve_named(&T::UnbondingReserveId::get(), &who, amount)?;
 
-			// free the tokens
-			T::Currency::unreserve(&who, T::Currency::reserved_balance(&who));
+			let votes = Self::compute_votes(&who);
+			Self::checkpoint_power(&who, votes);
+			<Voters<T>>::insert(who.clone(), votes);
 
-			Self::deposit_event(Event::VotesWithdrawn { who: who.clone() });
+			Self::deposit_event(Event::Unbonded { who, amount, until });
+
+			Ok(().into())
+		}
+
+		#[pallet::call_index(24)]
+		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::withdraw_unbonded())]
+		/// Release the unbonded stake whose unbonding period is over.
+		pub fn withdraw_unbonded(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			let mut chunks = <Unlocking<T>>::get(who.clone());
+			let amount = chunks.iter()
+				.filter(|(_, until)| *until <= now)
+				.fold(Zero::zero(), |total: BalanceOf<T>, (chunk, _)| total.saturating_add(*chunk));
+			ensure!(!amount.is_zero(), Error::<T>::NothingToWithdraw);
+
+			chunks.retain(|(_, until)| *until > now);
+			if chunks.is_empty() {
+				<Unlocking<T>>::remove(who.clone());
+			} else {
+				<Unlocking<T>>::insert(who.clone(), chunks);
+			}
+			T::Currency::unreserve_named(&T::UnbondingReserveId::get(), &who, amount);
+			Self::lock_unreserved_stake(&who);
+
+			Self::deposit_event(Event::UnbondedWithdrawn { who, amount });
+
+			Ok(().into())
+		}
+
+		#[pallet::call_index(25)]
+		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::top_up_rewards())]
+		/// Mint `amount` into the reward pot. The top-up fails when it is too low to create the
+		/// pot account.
+		pub fn top_up_rewards(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let amount = T::Currency::deposit_creating(&Self::reward_pot(), amount).peek();
+			ensure!(!amount.is_zero(), Error::<T>::InvalidTokenAmount);
+
+			Self::deposit_event(Event::RewardsToppedUp { amount });
+
+			Ok(().into())
+		}
+
+		#[pallet::call_index(26)]
+		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::claim_rewards())]
+		/// Claim the share of the reward of a finished proposal, in proportion to the votes spent
+		/// in it. The reward is the `ProposalReward` or the funds of the pot when the proposal
+		/// closed if lower, and the pot is never drained below the existential deposit. The ballots
+		/// committed to a secret proposal and never revealed get no reward.
+		pub fn claim_rewards(origin: OriginFor<T>, proposal_id: u32) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let (reward, spent_votes) = <ProposalRewards<T>>::get(proposal_id).ok_or(Error::<T>::NoReward)?;
+			ensure!(!<ClaimedRewards<T>>::contains_key(proposal_id, who.clone()), Error::<T>::RewardAlreadyClaimed);
+			ensure!(!<Commitments<T>>::contains_key(who.clone(), proposal_id), Error::<T>::NotVoted);
+			let votes = <VotedProposals<T>>::get(proposal_id, who.clone()).unwrap_or_default();
+			ensure!(!votes.is_zero(), Error::<T>::NotVoted);
+
+			let amount = multiply_by_rational_with_rounding(reward.saturated_into::<u128>(), votes, spent_votes, Rounding::Down)
+				.unwrap_or_default()
+				.saturated_into::<BalanceOf<T>>();
+			T::Currency::transfer(&Self::reward_pot(), &who, amount, ExistenceRequirement::KeepAlive)?;
+			<ClaimedRewards<T>>::insert(proposal_id, who.clone(), ());
+
+			Self::deposit_event(Event::RewardClaimed { who, proposal_id, amount });
+
+			Ok(().into())
+		}
+
+		#[pallet::call_index(27)]
+		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::reap_inactive_voter().saturating_add(Pallet::<T>::delegators_weight(3, 1).saturating_mul(T::MaxActiveProposals::get().into())))]
+		/// Remove a voter that skipped more than `MaxMissedProposals` consecutive proposals. The
+		/// `InactivitySlash` part of the register fee is slashed from its stake to the
+		/// `InactivitySlashHandler` and the voter is withdrawn. Any account can call it.
+		pub fn reap_inactive_voter(origin: OriginFor<T>, who: T::AccountId) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			ensure!(<Voters<T>>::contains_key(who.clone()), Error::<T>::NotAVoter);
+			ensure!(Self::is_inactive(&who), Error::<T>::VoterNotInactive);
+			Self::ensure_can_withdraw(&who)?;
+
+			// the fee is already in the pot, so its share is slashed from the stake
+			let to_slash = T::InactivitySlash::get().mul_floor(T::RegisterFee::get());
+			let (imbalance, not_slashed) = T::Currency::slash_reserved_named(&T::VotingReserveId::get(), &who, to_slash);
+			T::InactivitySlashHandler::on_unbalanced(imbalance);
+			Self::do_withdraw(&who);
+
+			Self::deposit_event(Event::InactiveVoterReaped { who, slashed: to_slash.saturating_sub(not_slashed) });
+
+			Ok(().into())
+		}
+
+		#[pallet::call_index(28)]
+		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::get_asset_votes())]
+		/// Hold tokens of an asset to get votes in the proposals of the asset
+		pub fn get_asset_votes(origin: OriginFor<T>, asset: AssetIdOf<T>, amount: AssetBalanceOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(<Voters<T>>::contains_key(who.clone()), Error::<T>::NotAVoter);
+			ensure!(!amount.is_zero(), Error::<T>::InvalidTokenAmount);
+
+			<T::Assets as fungibles::MutateHold<T::AccountId>>::hold(asset, &who, amount)?;
+			<AssetStakes<T>>::mutate(who.clone(), asset, |stake| *stake = stake.saturating_add(amount));
+			Self::checkpoint_asset_power(&who, asset, Self::asset_votes(&who, asset));
+
+			Self::deposit_event(Event::AssetHeld { who, asset, amount });
+
+			Ok(().into())
+		}
+
+		#[pallet::call_index(29)]
+		#[pallet::weight(<T::WeightInfo as weights::WeightInfo>::release_asset_votes())]
+		/// Release the tokens of an asset held by the voter, when the voter has not voted in any
+		/// open proposal of the asset and has no unrevealed ballot in a finished one.
+		pub fn release_asset_votes(origin: OriginFor<T>, asset: AssetIdOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let stake = <AssetStakes<T>>::get(who.clone(), asset);
+			ensure!(!stake.is_zero(), Error::<T>::NoAssetStake);
+			ensure!(
+				!<ActiveProposalIds<T>>::get().iter().any(|id| {
+					<ProposalAssets<T>>::get(id) == Some(asset) && Self::has_voted(who.clone(), *id)
+				}),
+				Error::<T>::VotesLocked
+			);
+			// the ballots committed to cancelled proposals have no asset left and are not counted
+			ensure!(
+				!<Commitments<T>>::iter_key_prefix(who.clone()).any(|id| <ProposalAssets<T>>::get(id) == Some(asset)),
+				Error::<T>::VotesLocked
+			);
+
+			// the holds are not named, so no more than the tokens held by this pallet are released
+			let amount = <T::Assets as fungibles::MutateHold<T::AccountId>>::release(asset, &who, stake, true)?;
+			<AssetStakes<T>>::remove(who.clone(), asset);
+			Self::checkpoint_asset_power(&who, asset, 0);
+
+			Self::deposit_event(Event::AssetReleased { who, asset, amount });
 
 			Ok(().into())
 		}
@@ -367,12 +1643,1419 @@ pub mod pallet {
 			Voters::<T>::get(who)
 		}
 
-		pub fn get_active_proposal() -> Option<CurrentProposal<T>> {
-			ActiveProposal::<T>::get()
+		pub fn get_active_proposal(id: u32) -> Option<CurrentProposal<T>> {
+			ActiveProposals::<T>::get(id)
 		}
 
-		pub fn get_closed_proposal(id: u32) -> Option<FinishedProposal<T>> {
-			FinishedProposals::<T>::get(id)
+		/// Create a new active proposal and return its id. The proposal starts at the current block
+		/// and lasts `MaxProposalDuration` blocks unless other start block or duration are given.
+		#[allow(clippy::too_many_arguments)]
+		fn do_set_proposal(
+			text: T::Hash,
+			vote_options: BoundedVec<Options<T::Hash>, T::MaxVecLen>,
+			ballot_type: BallotType,
+			secret: bool,
+			thresholds: Thresholds,
+			tie_break: TieBreak,
+			calls: BoundedVec<(u8, BoundedCallOf<T>), T::MaxVecLen>,
+			start_block: Option<T::BlockNumber>,
+			duration: Option<T::BlockNumber>,
+			asset: Option<AssetIdOf<T>>,
+		) -> Result<u32, DispatchError> {
+			Self::ensure_not_vetoed(&text)?;
+			Self::ensure_valid_ballot_type(ballot_type, &vote_options, &calls)?;
+			if let Some(asset) = asset {
+				ensure!(<T::Assets as fungibles::Inspect<T::AccountId>>::asset_exists(asset), Error::<T>::UnknownAsset);
+			}
+			let now = <frame_system::Pallet<T>>::block_number();
+			let start_block = start_block.unwrap_or(now);
+			ensure!(
+				start_block >= now && start_block <= now.saturating_add(T::MaxStartDelay::get()),
+				Error::<T>::InvalidStartBlock
+			);
+			let duration = duration.unwrap_or_else(T::MaxProposalDuration::get);
+			ensure!(
+				duration >= T::MinProposalDuration::get() && duration <= T::MaxProposalDuration::get(),
+				Error::<T>::InvalidDuration
+			);
+
+			let proposal_count = <ProposalCount<T>>::get();
+			let end_block = start_block.saturating_add(duration);
+			// create the proposal struct
+			let new_proposal = CurrentProposal::<T> {
+				id: proposal_count,
+				// the power changed in the creation block does not count, whether it comes before or
+				// after the proposal in the block
+				snapshot_block: now.saturating_sub(One::one()),
+				start_block,
+				end_block,
+				reveal_end_block: if secret { Some(end_block.saturating_add(T::RevealPeriod::get())) } else { None },
+				status: ProposalStatus::InProgress,
+				text: text,
+				ballot_type,
+				options: vote_options,
+				thresholds,
+				tie_break,
+				voters: 0,
+				spent_votes: 0,
+			};
+
+			// set the proposal as active
+			<ActiveProposalIds<T>>::try_mutate(|ids| ids.try_push(proposal_count))
+				.map_err(|_| Error::<T>::TooManyActiveProposals)?;
+			<ActiveProposals<T>>::insert(proposal_count, new_proposal);
+			if !calls.is_empty() {
+				<OptionCalls<T>>::insert(proposal_count, calls);
+			}
+			if let Some(asset) = asset {
+				<ProposalAssets<T>>::insert(proposal_count, asset);
+			}
+
+			Self::deposit_event(Event::ProposalCreated { id: proposal_count });
+
+			// increase the proposals count
+			<ProposalCount<T>>::mutate(|count| {
+				*count += 1;
+			});
+			Ok(proposal_count)
+		}
+
+		/// Check that the proposal text is not in the cooldown of a veto.
+		/// Check the parameters of the ballot type: a score proposal needs a maximum score and a
+		/// multi-winner proposal needs fewer seats than options and no calls.
+		fn ensure_valid_ballot_type(
+			ballot_type: BallotType,
+			vote_options: &BoundedVec<Options<T::Hash>, T::MaxVecLen>,
+			calls: &BoundedVec<(u8, BoundedCallOf<T>), T::MaxVecLen>,
+		) -> DispatchResult {
+			match ballot_type {
+				BallotType::Score(max_score) => ensure!(max_score > 0, Error::<T>::InvalidBallotType),
+				BallotType::SingleTransferable(seats) => {
+					ensure!(seats > 0 && (seats as usize) < vote_options.len(), Error::<T>::InvalidSeats);
+					ensure!(calls.is_empty(), Error::<T>::CallsNotAllowed);
+				},
+				_ => (),
+			}
+			Ok(())
+		}
+
+		fn ensure_not_vetoed(text: &T::Hash) -> DispatchResult {
+			let vetoed = <Blacklist<T>>::get(text).map_or(false, |until| until >= <frame_system::Pallet<T>>::block_number());
+			ensure!(!vetoed, Error::<T>::ProposalVetoed);
+			Ok(())
+		}
+
+		/// Check that every call is attached to a different option of the proposal.
+		fn ensure_valid_calls(vote_options: &BoundedVec<Options<T::Hash>, T::MaxVecLen>, calls: &BoundedVec<(u8, BoundedCallOf<T>), T::MaxVecLen>) -> DispatchResult {
+			for (position, (option_id, _)) in calls.iter().enumerate() {
+				ensure!(vote_options.iter().any(|o| o.id == *option_id), Error::<T>::InvalidOptionId);
+				ensure!(!calls[..position].iter().any(|(id, _)| id == option_id), Error::<T>::InvalidOptionId);
+			}
+			Ok(())
+		}
+
+		/// At the launch period, launch the most seconded queued proposal, the oldest one among
+		/// the equally seconded, when there is room for a new active proposal. The deposits of the
+		/// proposer and the seconders are refunded whether the proposal is launched or dropped
+		/// because it cannot be launched, except when its text was vetoed while queued: then the
+		/// deposit of the proposer is slashed. Returns the weight consumed.
+		fn launch_queued(now: T::BlockNumber) -> Weight {
+			let period = T::LaunchPeriod::get();
+			if period.is_zero() || !(now % period).is_zero() {
+				return Weight::zero();
+			}
+			let weight = T::DbWeight::get().reads(2);
+			if <ActiveProposalIds<T>>::get().len() >= T::MaxActiveProposals::get() as usize {
+				return weight;
+			}
+			let mut queue = <ProposalQueue<T>>::get();
+			let mut best: Option<(usize, usize)> = None;
+			for (position, pending) in queue.iter().enumerate() {
+				if best.map_or(true, |(_, seconds)| pending.seconds.len() > seconds) {
+					best = Some((position, pending.seconds.len()));
+				}
+			}
+			let position = match best {
+				Some((position, _)) => position,
+				None => return weight,
+			};
+			let pending = queue.remove(position);
+			<ProposalQueue<T>>::put(queue);
+
+			let launched = Self::do_set_proposal(
+				pending.text,
+				pending.options,
+				pending.ballot_type,
+				pending.secret,
+				pending.thresholds,
+				pending.tie_break,
+				pending.calls,
+				None,
+				None,
+				None,
+			);
+			match launched {
+				Ok(id) => {
+					T::Currency::unreserve_named(&T::DepositReserveId::get(), &pending.proposer, pending.deposit);
+					Self::deposit_event(Event::ProposalLaunched { index: pending.index, id });
+				},
+				Err(error) => {
+					if error == Error::<T>::ProposalVetoed.into() {
+						let (_, not_slashed) = T::Currency::slash_reserved_named(&T::DepositReserveId::get(), &pending.proposer, pending.deposit);
+						let amount = pending.deposit.saturating_sub(not_slashed);
+						Self::deposit_event(Event::ProposalSlashed { index: pending.index, who: pending.proposer.clone(), amount });
+					} else {
+						// the proposal is dropped, so the deposit is not kept reserved for nothing
+						T::Currency::unreserve_named(&T::DepositReserveId::get(), &pending.proposer, pending.deposit);
+					}
+					Self::deposit_event(Event::LaunchFailed { index: pending.index, error });
+				},
+			}
+			// the voters that seconded the proposal get their deposits back
+			for seconder in pending.seconds.iter() {
+				T::Currency::unreserve_named(&T::DepositReserveId::get(), seconder, pending.deposit);
+			}
+
+			weight
+				.saturating_add(<T::WeightInfo as weights::WeightInfo>::set_proposal())
+				.saturating_add(T::DbWeight::get().reads_writes(1, 2))
+				.saturating_add(T::DbWeight::get().reads_writes(T::MaxSeconds::get().into(), T::MaxSeconds::get().into()))
+		}
+
+		/// Tally an expired proposal, move it to the finished proposals and emit the result.
+		fn do_end_proposal(active_proposal: CurrentProposal<T>) {
+			let mut ranking = BoundedVec::default();
+			let mut pairwise = BoundedVec::default();
+			let mut winners = BoundedVec::default();
+			let (mut winner_index, mut winner_votes, rounds) = match active_proposal.ballot_type {
+				BallotType::Split | BallotType::Approval | BallotType::Score(_) => {
+					let (winner_index, winner_votes) = Self::tally_split(&active_proposal.options);
+					(winner_index, winner_votes, BoundedVec::default())
+				},
+				BallotType::RankedChoice => Self::tally_instant_runoff(&active_proposal),
+				BallotType::Condorcet => {
+					let (winner_index, winner_votes, schulze_ranking, matrix) = Self::tally_schulze(&active_proposal);
+					ranking = schulze_ranking;
+					pairwise = matrix;
+					(winner_index, winner_votes, BoundedVec::default())
+				},
+				BallotType::SingleTransferable(seats) => {
+					let (elected, stv_rounds) = Self::tally_single_transferable(&active_proposal, seats);
+					// the first elected option and its votes in the round it was elected
+					let winner_votes = stv_rounds.iter()
+						.find(|round| !round.elected.is_empty())
+						.and_then(|round| round.tallies.iter().find(|t| t.id == round.elected[0]))
+						.map_or(0, |t| t.votes);
+					let winner_index = elected.first().copied().unwrap_or_default();
+					winners = elected;
+					(winner_index, winner_votes, stv_rounds)
+				},
+			};
+
+			// the share of the winner is computed over the votes counted in the last instant-runoff
+			// round, and over the first preferences in a multi-winner proposal
+			let total_votes = active_proposal.options.iter().fold(0u128, |total, o| total.saturating_add(o.votes));
+			let counted_votes = match active_proposal.ballot_type {
+				BallotType::SingleTransferable(_) => total_votes,
+				_ => rounds.last().map_or(total_votes, |round| {
+					round.tallies.iter().fold(0u128, |total, t| total.saturating_add(t.votes))
+				}),
+			};
+			let thresholds = active_proposal.thresholds;
+			let rejection = if total_votes.is_zero() || total_votes < thresholds.min_votes {
+				Some(RejectionReason::NotEnoughVotes)
+			} else if active_proposal.voters < thresholds.min_voters {
+				Some(RejectionReason::NotEnoughVoters)
+			} else if thresholds.approval.map_or(false, |approval| winner_votes < approval.mul_ceil(counted_votes)) {
+				Some(RejectionReason::NotEnoughApproval)
+			} else {
+				None
+			};
+			let mut status = match rejection {
+				None => ProposalStatus::Finished,
+				Some(RejectionReason::NotEnoughApproval) => ProposalStatus::Rejected,
+				Some(_) => ProposalStatus::NoQuorum,
+			};
+
+			let tied = if rejection.is_none() { Self::tied_options(&active_proposal, &rounds, &pairwise) } else { Vec::new() };
+			if !tied.is_empty() {
+				match active_proposal.tie_break {
+					TieBreak::Random => {
+						let winner = tied[Self::random_index(active_proposal.id, tied.len())];
+						winner_index = winner.id;
+						winner_votes = winner.votes;
+					},
+					TieBreak::Declare | TieBreak::Runoff | TieBreak::Origin => status = ProposalStatus::Tied,
+				}
+			}
+
+			let new_finished_proposal = FinishedProposal::<T> {
+				id: active_proposal.id,
+				text: active_proposal.text,
+				end_block: active_proposal.end_block,
+				status,
+				ballot_type: active_proposal.ballot_type,
+				options_votes: active_proposal.options.clone(),
+				winner_index: winner_index,
+				rounds,
+				tie_break: active_proposal.tie_break,
+				tied: BoundedVec::truncate_from(tied.clone()),
+				ranking,
+				pairwise,
+				winners,
+			};
+
+			// add the finished proposal to the finished proposals list
+			<FinishedProposals<T>>::insert(active_proposal.id, new_finished_proposal);
+			Self::record_recent_proposal(active_proposal.id, active_proposal.snapshot_block);
+			// the voters claim their share of the reward later, out of the funds of the pot when
+			// the proposal closes
+			let pot = T::Currency::free_balance(&Self::reward_pot()).saturating_sub(T::Currency::minimum_balance());
+			let reward = T::ProposalReward::get().min(pot);
+			if !reward.is_zero() && !active_proposal.spent_votes.is_zero() {
+				<ProposalRewards<T>>::insert(active_proposal.id, (reward, active_proposal.spent_votes));
+			}
+			// remove the proposal from the active ones
+			<ActiveProposals<T>>::remove(active_proposal.id);
+			<ActiveProposalIds<T>>::mutate(|ids| ids.retain(|id| *id != active_proposal.id));
+			// the ballots cannot change anymore, `on_idle` removes them
+			<ClosedBallots<T>>::insert(active_proposal.id, false);
+
+			let option_calls = <OptionCalls<T>>::take(active_proposal.id);
+			let asset = <ProposalAssets<T>>::get(active_proposal.id);
+			if let Some(reason) = rejection {
+				Self::deposit_event(Event::ProposalRejected { id: active_proposal.id, reason });
+				return;
+			}
+
+			if status == ProposalStatus::Tied {
+				let tied_ids: Vec<u8> = tied.iter().map(|t| t.id).collect();
+				let runoff = match active_proposal.tie_break {
+					TieBreak::Runoff => Self::open_runoff(&active_proposal, &tied_ids, option_calls, asset),
+					TieBreak::Origin => {
+						// keep the calls until the origin picks the winner
+						if !option_calls.is_empty() {
+							<OptionCalls<T>>::insert(active_proposal.id, option_calls);
+						}
+						None
+					},
+					_ => None,
+				};
+				Self::deposit_event(Event::ProposalTied { id: active_proposal.id, options: BoundedVec::truncate_from(tied_ids), runoff });
+				return;
+			}
+
+			Self::deposit_event(Event::ProposalFinished { id: active_proposal.id, winner_index: winner_index, winner_votes: winner_votes });
+			Self::schedule_enactment(active_proposal.id, winner_index, option_calls);
+		}
+
+		/// Schedule the call attached to the winner option, if any, and request its preimage until
+		/// the enactment.
+		fn schedule_enactment(id: u32, winner_index: u8, option_calls: BoundedVec<(u8, BoundedCallOf<T>), T::MaxVecLen>) {
+			let winner_call = option_calls.into_iter().find(|(option_id, _)| *option_id == winner_index);
+			if let Some((_, call)) = winner_call {
+				T::Preimages::hold(&call);
+				let when = <frame_system::Pallet<T>>::block_number() + T::EnactmentDelay::get().max(One::one());
+				let when = Self::push_enactment(when, id, call);
+				Self::deposit_event(Event::ProposalScheduled { id, when });
+			}
+		}
+
+		/// Add the call to the enactments of `when`, or of the first later block with room when
+		/// `when` is full, and return the block. The tie resolutions can add calls to a block on top
+		/// of the proposals finished in it, so a block can run out of room.
+		fn push_enactment(mut when: T::BlockNumber, id: u32, call: BoundedCallOf<T>) -> T::BlockNumber {
+			let mut enactment = (id, call);
+			loop {
+				match <Enactments<T>>::try_mutate(when, |calls| calls.try_push(enactment)) {
+					Ok(()) => return when,
+					Err(rejected) => {
+						enactment = rejected;
+						when = when.saturating_add(One::one());
+					},
+				}
+			}
+		}
+
+		/// The options tied for the lead with their votes, empty when there is no tie. A
+		/// ranked-choice proposal is tied when its last elimination was between the leading options,
+		/// a condorcet proposal when more than one option is not beaten by any other. The ties of a
+		/// multi-winner proposal are broken by the order of the options.
+		fn tied_options(proposal: &CurrentProposal<T>, rounds: &[RunoffRound<T>], pairwise: &[u128]) -> Vec<VoteStruct> {
+			if let BallotType::SingleTransferable(_) = proposal.ballot_type {
+				return Vec::new();
+			}
+			if proposal.ballot_type == BallotType::Condorcet {
+				let n = proposal.options.len();
+				let strengths = Self::schulze_strengths(n, pairwise);
+				let tied: Vec<VoteStruct> = (0..n)
+					.filter(|i| (0..n).all(|j| strengths[j * n + i] <= strengths[i * n + j]))
+					.map(|i| VoteStruct { id: proposal.options[i].id, votes: Self::closest_contest(proposal, pairwise, i) })
+					.collect();
+				return if tied.len() > 1 { tied } else { Vec::new() };
+			}
+			let tallies: Vec<VoteStruct> = match rounds {
+				[] => proposal.options.iter().map(|o| VoteStruct { id: o.id, votes: o.votes }).collect(),
+				[.., previous, last] if last.tallies.len() == 1 => previous.tallies.to_vec(),
+				_ => return Vec::new(),
+			};
+			let top = tallies.iter().map(|t| t.votes).max().unwrap_or_default();
+			let tied: Vec<VoteStruct> = tallies.into_iter().filter(|t| t.votes == top).collect();
+			if tied.len() > 1 { tied } else { Vec::new() }
+		}
+
+		/// A random index lower than `len` for the proposal `id`.
+		fn random_index(id: u32, len: usize) -> usize {
+			let (seed, _) = T::Randomness::random(&(b"voting/tie", id).encode());
+			let random = u32::decode(&mut TrailingZeroInput::new(seed.as_ref())).unwrap_or_default();
+			random as usize % len
+		}
+
+		/// Open a proposal among the tied options of `proposal`, with their calls, and return its id.
+		/// A runoff that ties again is declared as tied.
+		fn open_runoff(
+			proposal: &CurrentProposal<T>,
+			tied: &[u8],
+			option_calls: BoundedVec<(u8, BoundedCallOf<T>), T::MaxVecLen>,
+			asset: Option<AssetIdOf<T>>,
+		) -> Option<u32> {
+			let options: Vec<Options<T::Hash>> = proposal.options.iter()
+				.filter(|o| tied.contains(&o.id))
+				.map(|o| Options { id: o.id, votes: 0, text: o.text })
+				.collect();
+			let calls: Vec<(u8, BoundedCallOf<T>)> = option_calls.into_iter().filter(|(id, _)| tied.contains(id)).collect();
+			// qed, the tied proposal already left a free slot in the active proposals
+			Self::do_set_proposal(
+				proposal.text,
+				BoundedVec::truncate_from(options),
+				proposal.ballot_type,
+				proposal.reveal_end_block.is_some(),
+				proposal.thresholds,
+				TieBreak::Declare,
+				BoundedVec::truncate_from(calls),
+				None,
+				None,
+				asset,
+			)
+			.ok()
+		}
+
+		/// Dispatch the calls of the winner options scheduled for `now` with the voting origin, up
+		/// to `MaxEnactmentWeight`, and drop their preimages. The calls that do not fit are moved to
+		/// the next block. Returns the weight consumed.
+		fn enact_proposals(now: T::BlockNumber) -> Weight {
+			let max_weight = T::MaxEnactmentWeight::get();
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+			let mut dispatched = Weight::zero();
+			for (id, bounded_call) in <Enactments<T>>::take(now) {
+				weight = weight.saturating_add(T::DbWeight::get().reads(1));
+				let result = match T::Preimages::peek(&bounded_call) {
+					Ok((call, _)) => {
+						let call_weight = call.get_dispatch_info().weight;
+						if call_weight.any_gt(max_weight) {
+							Err(Error::<T>::EnactmentTooHeavy.into())
+						} else if dispatched.saturating_add(call_weight).any_gt(max_weight) {
+							weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+							let when = Self::push_enactment(now.saturating_add(One::one()), id, bounded_call);
+							Self::deposit_event(Event::ProposalScheduled { id, when });
+							continue;
+						} else {
+							dispatched = dispatched.saturating_add(call_weight);
+							call.dispatch(RawOrigin::Proposal(id).into()).map(|_| ()).map_err(|e| e.error)
+						}
+					},
+					Err(error) => Err(error),
+				};
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+				T::Preimages::drop(&bounded_call);
+				Self::deposit_event(Event::ProposalEnacted { id, result });
+			}
+			weight.saturating_add(dispatched)
+		}
+
+		/// Search the most voted option.
+		fn tally_split(options: &BoundedVec<Options<T::Hash>, T::MaxVecLen>) -> (u8, u128) {
+			let mut winner_index:u8 = 0;
+			let mut winner_votes:u128 = 0;
+			for i in options.iter() {
+				if i.votes > winner_votes {
+					winner_index = i.id as u8;
+					winner_votes = i.votes;
+				}
+			}
+			(winner_index, winner_votes)
+		}
+
+		/// Run the instant-runoff rounds of a ranked-choice proposal. Every round counts each ballot
+		/// for its highest ranked option still in the race, an option with more than half of the
+		/// counted votes wins, otherwise the least voted option is eliminated. On a tie the option
+		/// listed last is eliminated.
+		fn tally_instant_runoff(proposal: &CurrentProposal<T>) -> (u8, u128, BoundedVec<RunoffRound<T>, T::MaxVecLen>) {
+			let ballots = <RankedBallots<T>>::take(proposal.id);
+			let mut continuing: Vec<u8> = proposal.options.iter().map(|o| o.id).collect();
+			let mut rounds: Vec<RunoffRound<T>> = Vec::new();
+
+			loop {
+				let mut tallies: Vec<VoteStruct> =
+					continuing.iter().map(|id| VoteStruct { id: *id, votes: 0 }).collect();
+				let mut counted = 0u128;
+				for ballot in ballots.iter() {
+					if let Some(choice) = ballot.ranking.iter().find(|id| continuing.contains(id)) {
+						if let Some(tally) = tallies.iter_mut().find(|t| t.id == *choice) {
+							tally.votes = tally.votes.saturating_add(ballot.votes);
+							counted = counted.saturating_add(ballot.votes);
+						}
+					}
+				}
+
+				let leader = tallies.iter().fold(None, |best: Option<VoteStruct>, t| match best {
+					Some(b) if b.votes >= t.votes => Some(b),
+					_ => Some(*t),
+				});
+				let leader = match leader {
+					Some(leader) => leader,
+					None => return (0, 0, BoundedVec::truncate_from(rounds)),
+				};
+
+				if continuing.len() == 1 || leader.votes.saturating_mul(2) > counted {
+					rounds.push(RunoffRound { tallies: BoundedVec::truncate_from(tallies), elected: BoundedVec::default(), eliminated: None });
+					return (leader.id, leader.votes, BoundedVec::truncate_from(rounds));
+				}
+
+				// qed, there are at least two options in the race
+				let loser = tallies.iter().rev().min_by_key(|t| t.votes).map(|t| t.id).unwrap_or_default();
+				continuing.retain(|id| *id != loser);
+				rounds.push(RunoffRound { tallies: BoundedVec::truncate_from(tallies), elected: BoundedVec::default(), eliminated: Some(loser) });
+			}
+		}
+
+		/// Fill the seats of a multi-winner proposal by single transferable vote. Every round counts
+		/// each ballot, at its current value, for its highest ranked option still in the race. The
+		/// options that reach the Droop quota are elected and the ballots counted for them go on
+		/// with the fraction of their value that exceeds the quota. When nobody reaches the quota
+		/// the least voted option is eliminated, the option listed last on a tie. The options left
+		/// are elected when they are no more than the free seats.
+		fn tally_single_transferable(proposal: &CurrentProposal<T>, seats: u8) -> (BoundedVec<u8, T::MaxVecLen>, BoundedVec<RunoffRound<T>, T::MaxVecLen>) {
+			let ballots = <RankedBallots<T>>::take(proposal.id);
+			let mut values: Vec<u128> = ballots.iter().map(|b| b.votes).collect();
+			let total = values.iter().fold(0u128, |total, v| total.saturating_add(*v));
+			let quota = total / (seats as u128 + 1) + 1;
+			let mut continuing: Vec<u8> = proposal.options.iter().map(|o| o.id).collect();
+			let mut elected: Vec<u8> = Vec::new();
+			let mut rounds: Vec<RunoffRound<T>> = Vec::new();
+
+			while elected.len() < seats as usize && !continuing.is_empty() {
+				let choices: Vec<Option<u8>> = ballots.iter()
+					.map(|b| b.ranking.iter().find(|id| continuing.contains(id)).copied())
+					.collect();
+				let mut tallies: Vec<VoteStruct> =
+					continuing.iter().map(|id| VoteStruct { id: *id, votes: 0 }).collect();
+				for (choice, value) in choices.iter().zip(values.iter()) {
+					if let Some(tally) = tallies.iter_mut().find(|t| Some(t.id) == *choice) {
+						tally.votes = tally.votes.saturating_add(*value);
+					}
+				}
+
+				let free_seats = seats as usize - elected.len();
+				let mut round_elected: Vec<VoteStruct> = if continuing.len() <= free_seats {
+					tallies.clone()
+				} else {
+					tallies.iter().filter(|t| t.votes >= quota).copied().collect()
+				};
+				// the sort is stable, so the ties keep the order of the options
+				round_elected.sort_by(|a, b| b.votes.cmp(&a.votes));
+				round_elected.truncate(free_seats);
+
+				if round_elected.is_empty() {
+					// qed, there are more options in the race than free seats
+					let loser = tallies.iter().rev().min_by_key(|t| t.votes).map(|t| t.id).unwrap_or_default();
+					continuing.retain(|id| *id != loser);
+					rounds.push(RunoffRound { tallies: BoundedVec::truncate_from(tallies), elected: BoundedVec::default(), eliminated: Some(loser) });
+					continue;
+				}
+
+				// transfer the surplus of the elected options
+				for winner in round_elected.iter() {
+					let surplus = winner.votes.saturating_sub(quota);
+					for (choice, value) in choices.iter().zip(values.iter_mut()) {
+						if *choice == Some(winner.id) {
+							*value = multiply_by_rational_with_rounding(*value, surplus, winner.votes, Rounding::Down)
+								.unwrap_or_default();
+						}
+					}
+					continuing.retain(|id| *id != winner.id);
+					elected.push(winner.id);
+				}
+				let round_elected: Vec<u8> = round_elected.iter().map(|t| t.id).collect();
+				rounds.push(RunoffRound {
+					tallies: BoundedVec::truncate_from(tallies),
+					elected: BoundedVec::truncate_from(round_elected),
+					eliminated: None,
+				});
+			}
+			(BoundedVec::truncate_from(elected), BoundedVec::truncate_from(rounds))
+		}
+
+		/// Rank the options of a condorcet proposal by the Schulze method. An option beats another
+		/// when its strongest path to it is stronger than the reverse one, the options are ranked by
+		/// the number of options they beat and the ones listed first go first on a tie. The winner
+		/// votes are the votes that prefer the winner in its closest pairwise contest.
+		fn tally_schulze(proposal: &CurrentProposal<T>) -> (u8, u128, BoundedVec<u8, T::MaxVecLen>, BoundedVec<u128, MaxPairwiseEntries<T>>) {
+			let n = proposal.options.len();
+			let mut pairwise = <PairwiseMatrices<T>>::take(proposal.id);
+			if pairwise.len() != n * n {
+				pairwise = BoundedVec::truncate_from(vec![0; n * n]);
+			}
+			let strengths = Self::schulze_strengths(n, &pairwise);
+
+			let mut order: Vec<(usize, usize)> = (0..n)
+				.map(|i| (i, (0..n).filter(|j| strengths[i * n + j] > strengths[j * n + i]).count()))
+				.collect();
+			// the sort is stable, so the ties keep the order of the options
+			order.sort_by(|a, b| b.1.cmp(&a.1));
+			let ranking: Vec<u8> = order.iter().map(|(i, _)| proposal.options[*i].id).collect();
+
+			match order.first() {
+				Some((winner, _)) => {
+					let winner_votes = Self::closest_contest(proposal, &pairwise, *winner);
+					(proposal.options[*winner].id, winner_votes, BoundedVec::truncate_from(ranking), pairwise)
+				},
+				None => (0, 0, BoundedVec::default(), pairwise),
+			}
+		}
+
+		/// Strength of the strongest path between every ordered pair of `n` options, from their
+		/// pairwise preferences. A path is as strong as its weakest link and only the pairwise
+		/// wins are links.
+		fn schulze_strengths(n: usize, pairwise: &[u128]) -> Vec<u128> {
+			let mut strengths = vec![0u128; n * n];
+			if pairwise.len() != n * n {
+				return strengths;
+			}
+			for i in 0..n {
+				for j in 0..n {
+					if i != j && pairwise[i * n + j] > pairwise[j * n + i] {
+						strengths[i * n + j] = pairwise[i * n + j];
+					}
+				}
+			}
+			for k in 0..n {
+				for i in (0..n).filter(|i| *i != k) {
+					for j in (0..n).filter(|j| *j != k && *j != i) {
+						let through_k = strengths[i * n + k].min(strengths[k * n + j]);
+						strengths[i * n + j] = strengths[i * n + j].max(through_k);
+					}
+				}
+			}
+			strengths
+		}
+
+		/// Votes that prefer the option at `position` over its strongest rival. The only option of
+		/// a proposal gets its first preferences.
+		fn closest_contest(proposal: &CurrentProposal<T>, pairwise: &[u128], position: usize) -> u128 {
+			let n = proposal.options.len();
+			(0..n)
+				.filter(|j| *j != position)
+				.map(|j| pairwise.get(position * n + j).copied().unwrap_or_default())
+				.min()
+				.unwrap_or(proposal.options[position].votes)
+		}
+
+		/// Add `votes` to the pairwise preferences of a condorcet proposal, or remove them when
+		/// `add` is false. A ranked option is preferred over the options ranked after it and over
+		/// the unranked ones.
+		fn update_pairwise(proposal: &CurrentProposal<T>, ranking: &[u8], votes: u128, add: bool) {
+			let ids: Vec<u8> = proposal.options.iter().map(|o| o.id).collect();
+			let n = ids.len();
+			<PairwiseMatrices<T>>::mutate(proposal.id, |pairwise| {
+				if pairwise.len() != n * n {
+					*pairwise = BoundedVec::truncate_from(vec![0; n * n]);
+				}
+				for (position, preferred) in ranking.iter().enumerate() {
+					let i = match ids.iter().position(|id| id == preferred) {
+						Some(i) => i,
+						None => continue,
+					};
+					for (j, other) in ids.iter().enumerate() {
+						if ranking[..=position].contains(other) {
+							continue;
+						}
+						let entry = &mut pairwise[i * n + j];
+						*entry = if add { entry.saturating_add(votes) } else { entry.saturating_sub(votes) };
+					}
+				}
+			});
+		}
+
+		/// Add a split, approval or score ballot to the proposal tallies.
+		fn do_vote(who: &T::AccountId, mut active_proposal: CurrentProposal<T>, votes: &BoundedVec<VoteStruct,T::MaxVecLen>, available_votes: u128) -> DispatchResult {
+			match active_proposal.ballot_type {
+				BallotType::Split => (),
+				BallotType::Approval => return Self::do_vote_scored(who, active_proposal, votes, available_votes, 1),
+				BallotType::Score(max_score) => return Self::do_vote_scored(who, active_proposal, votes, available_votes, max_score),
+				BallotType::RankedChoice | BallotType::Condorcet | BallotType::SingleTransferable(_) =>
+					return Err(Error::<T>::InvalidBallotType.into()),
+			}
+
+			// counter to check the used votes
+			let mut used_votes = 0u128;
+
+			for i in votes {
+			 	let proposal_voted = i.id;
+				if u32::from(proposal_voted) >= T::MaxVecLen::get() {
+					return Err(Error::<T>::InvalidOptionId.into());
+				}
+				for j in 0..active_proposal.options.len() {
+					if active_proposal.options[j].id == proposal_voted {
+						active_proposal.options[j].votes = active_proposal.options[j].votes
+							.checked_add(i.votes)
+							.ok_or(ArithmeticError::Overflow)?;
+						used_votes = used_votes.checked_add(i.votes).ok_or(ArithmeticError::Overflow)?;
+					}
+				}
+			}
+
+			// verify that the user not vote more than he can
+			ensure!(used_votes <= available_votes, Error::<T>::NotEnoughVotes);
+			// set this proposal as voted
+			Self::record_spent_votes(&mut active_proposal, who, used_votes);
+			<CastVotes<T>>::insert(active_proposal.id, who, CastVote { ballot: Ballot::Split(votes.clone()), power: available_votes });
+			// update the active proposal
+			active_proposal.voters = active_proposal.voters.saturating_add(1);
+			<ActiveProposals<T>>::insert(active_proposal.id, active_proposal);
+			Ok(())
+		}
+
+		/// Add an approval or score ballot to the proposal tallies. Every option gets the score given
+		/// by the voter, up to `max_score`, times the votes of the voter. An approval is a score of 1.
+		fn do_vote_scored(who: &T::AccountId, mut active_proposal: CurrentProposal<T>, scores: &BoundedVec<VoteStruct,T::MaxVecLen>, available_votes: u128, max_score: u8) -> DispatchResult {
+			// every scored option must exist and appear only once
+			for (position, score) in scores.iter().enumerate() {
+				ensure!(active_proposal.options.iter().any(|o| o.id == score.id), Error::<T>::InvalidOptionId);
+				ensure!(!scores[..position].iter().any(|s| s.id == score.id), Error::<T>::InvalidOptionId);
+				ensure!(score.votes <= max_score.into(), Error::<T>::InvalidScore);
+			}
+
+			for score in scores.iter() {
+				for option in active_proposal.options.iter_mut() {
+					if option.id == score.id {
+						option.votes = option.votes.saturating_add(score.votes.saturating_mul(available_votes));
+					}
+				}
+			}
+
+			Self::record_spent_votes(&mut active_proposal, who, available_votes);
+			<CastVotes<T>>::insert(active_proposal.id, who, CastVote { ballot: Ballot::Scored(scores.clone()), power: available_votes });
+			active_proposal.voters = active_proposal.voters.saturating_add(1);
+			<ActiveProposals<T>>::insert(active_proposal.id, active_proposal);
+			Ok(())
+		}
+
+		/// Add a ranking backed by `votes` to a ranked-choice, condorcet or multi-winner proposal.
+		fn do_vote_ranked(who: &T::AccountId, mut active_proposal: CurrentProposal<T>, ranking: &BoundedVec<u8,T::MaxVecLen>, votes: u128) -> DispatchResult {
+			ensure!(
+				matches!(
+					active_proposal.ballot_type,
+					BallotType::RankedChoice | BallotType::Condorcet | BallotType::SingleTransferable(_)
+				),
+				Error::<T>::InvalidBallotType
+			);
+			ensure!(!ranking.is_empty(), Error::<T>::InvalidRanking);
+
+			// every ranked option must exist and appear only once
+			for (position, option_id) in ranking.iter().enumerate() {
+				ensure!(active_proposal.options.iter().any(|o| o.id == *option_id), Error::<T>::InvalidOptionId);
+				ensure!(!ranking[..position].contains(option_id), Error::<T>::InvalidRanking);
+			}
+
+			if active_proposal.ballot_type == BallotType::Condorcet {
+				Self::update_pairwise(&active_proposal, ranking, votes, true);
+			} else {
+				// group the ballot with the equal rankings
+				<RankedBallots<T>>::try_mutate(active_proposal.id, |ballots| -> DispatchResult {
+					match ballots.iter_mut().find(|b| b.ranking == *ranking) {
+						Some(ballot) => ballot.votes = ballot.votes.saturating_add(votes),
+						None => ballots
+							.try_push(RankedBallot { ranking: ranking.clone(), votes })
+							.map_err(|_| Error::<T>::TooManyRankings)?,
+					}
+					Ok(())
+				})?;
+			}
+
+			// the options keep track of the first preferences
+			for option in active_proposal.options.iter_mut() {
+				if option.id == ranking[0] {
+					option.votes = option.votes.saturating_add(votes);
+				}
+			}
+
+			Self::record_spent_votes(&mut active_proposal, who, votes);
+			<CastVotes<T>>::insert(active_proposal.id, who, CastVote { ballot: Ballot::Ranked(ranking.clone()), power: votes });
+			active_proposal.voters = active_proposal.voters.saturating_add(1);
+			<ActiveProposals<T>>::insert(active_proposal.id, active_proposal);
+			Ok(())
+		}
+
+		/// Votes of `who` plus the votes delegated to it. When a proposal id and its snapshot block
+		/// are given, the votes are the ones at the snapshot and the delegators that voted directly
+		/// in it are not counted.
+		fn effective_votes(who: &T::AccountId, proposal: Option<(u32, T::BlockNumber)>, depth: u32) -> u128 {
+			let own_votes = match proposal {
+				Some((_, snapshot_block)) => Self::votes_at(who, snapshot_block),
+				None => <Voters<T>>::get(who).unwrap_or_default(),
+			};
+			if depth >= T::MaxDelegationDepth::get() {
+				return own_votes;
+			}
+			<Delegators<T>>::get(who)
+				.iter()
+				.filter(|d| proposal.map_or(true, |(id, _)| !<VotedProposals<T>>::contains_key(id, *d)))
+				.fold(own_votes, |votes, d| votes.saturating_add(Self::effective_votes(d, proposal, depth + 1)))
+		}
+
+		/// Vote power of `who` at the given block. A voter with no checkpoints has not changed its
+		/// power since it was registered, or since the checkpoints were introduced, so its current
+		/// votes are in force at every block.
+		fn votes_at(who: &T::AccountId, block: T::BlockNumber) -> u128 {
+			let checkpoints = <PowerCheckpoints<T>>::get(who);
+			if checkpoints.is_empty() {
+				return <Voters<T>>::get(who).unwrap_or_default();
+			}
+			Self::power_at(&checkpoints, block)
+		}
+
+		/// Vote power given by the tokens of `asset` held by `who` at the given block.
+		fn asset_votes_at(who: &T::AccountId, asset: AssetIdOf<T>, block: T::BlockNumber) -> u128 {
+			Self::power_at(&<AssetPowerCheckpoints<T>>::get(who, asset), block)
+		}
+
+		/// Power in force at the given block in a list of checkpoints, the oldest first.
+		fn power_at(checkpoints: &[(T::BlockNumber, u128)], block: T::BlockNumber) -> u128 {
+			checkpoints.iter().rev().find(|(since, _)| *since <= block).map_or(0, |(_, votes)| *votes)
+		}
+
+		/// Record the new vote power of `who`, before `Voters` is updated. The first checkpoint of a
+		/// voter registered before the checkpoints existed is seeded with the votes it had since
+		/// the genesis block, so the snapshots taken before the change keep seeing them.
+		fn checkpoint_power(who: &T::AccountId, votes: u128) {
+			<PowerCheckpoints<T>>::mutate(who, |checkpoints| {
+				if checkpoints.is_empty() {
+					match <Voters<T>>::get(who) {
+						Some(previous) if !previous.is_zero() =>
+							*checkpoints = BoundedVec::truncate_from(vec![(Zero::zero(), previous)]),
+						_ => {},
+					}
+				}
+				Self::push_checkpoint(checkpoints, votes)
+			});
+		}
+
+		/// Record the new vote power given by the tokens of `asset` held by `who`.
+		fn checkpoint_asset_power(who: &T::AccountId, asset: AssetIdOf<T>, votes: u128) {
+			<AssetPowerCheckpoints<T>>::mutate(who, asset, |checkpoints| Self::push_checkpoint(checkpoints, votes));
+		}
+
+		/// Add the power since the current block to the checkpoints. Only the checkpoints still
+		/// needed by the snapshots of the open proposals, and of the recent ones to check the
+		/// inactivity, are kept, plus the power in force before the current block for the proposals
+		/// created later in the block.
+		fn push_checkpoint(checkpoints: &mut BoundedVec<(T::BlockNumber, u128), MaxPowerCheckpoints<T>>, votes: u128) {
+			let now = <frame_system::Pallet<T>>::block_number();
+			let snapshots: Vec<T::BlockNumber> = <ActiveProposalIds<T>>::get()
+				.iter()
+				.filter_map(|id| <ActiveProposals<T>>::get(id))
+				.map(|p| p.snapshot_block)
+				.chain(<RecentProposals<T>>::get().iter().map(|(_, snapshot_block)| *snapshot_block))
+				.collect();
+			let latest = checkpoints.iter().rposition(|(since, _)| *since < now);
+			let mut kept: Vec<(T::BlockNumber, u128)> = Vec::new();
+			for (position, (since, power)) in checkpoints.iter().enumerate() {
+				let until = checkpoints.get(position + 1).map(|(next, _)| *next);
+				let needed = Some(position) == latest ||
+					snapshots.iter().any(|s| since <= s && until.map_or(true, |next| next > *s));
+				if needed && *since < now {
+					kept.push((*since, *power));
+				}
+			}
+			kept.push((now, votes));
+			*checkpoints = BoundedVec::truncate_from(kept);
+		}
+
+		/// Weight of walking `MaxTotalDelegators` delegators with the given storage reads and writes
+		/// for each one.
+		pub(crate) fn delegators_weight(reads: u64, writes: u64) -> Weight {
+			T::DbWeight::get().reads_writes(reads, writes).saturating_mul(T::MaxTotalDelegators::get().into())
+		}
+
+		/// Length of the longest chain of delegations ending in `who`.
+		fn delegation_height(who: &T::AccountId, depth: u32) -> u32 {
+			if depth >= T::MaxDelegationDepth::get() {
+				return 0;
+			}
+			<Delegators<T>>::get(who)
+				.iter()
+				.map(|d| 1 + Self::delegation_height(d, depth + 1))
+				.max()
+				.unwrap_or(0)
+		}
+
+		/// Remove `votes` delegated by `who` from the ballot of the closest delegate up the chain
+		/// that already voted in the proposal.
+		fn override_delegation(who: &T::AccountId, proposal_id: u32, votes: u128) {
+			// the delegated votes are not used in asset proposals
+			if votes.is_zero() || <ProposalAssets<T>>::contains_key(proposal_id) {
+				return;
+			}
+			let mut current = <Delegations<T>>::get(who);
+			let mut depth = 0u32;
+			while let Some(delegate) = current {
+				if depth >= T::MaxDelegationDepth::get() {
+					return;
+				}
+				if <VotedProposals<T>>::contains_key(proposal_id, delegate.clone()) {
+					Self::reduce_cast_vote(&delegate, proposal_id, votes);
+					return;
+				}
+				current = <Delegations<T>>::get(delegate);
+				depth += 1;
+			}
+		}
+
+		/// Record the own votes of the delegators of `who` counted in the ballot it casts in a public
+		/// proposal without an asset.
+		fn record_delegated_votes(who: &T::AccountId, proposal: &CurrentProposal<T>) {
+			if proposal.reveal_end_block.is_none() && !<ProposalAssets<T>>::contains_key(proposal.id) {
+				Self::record_delegators(who, proposal.id, proposal.snapshot_block, 0);
+			}
+		}
+
+		/// Walk the delegators of `who` that did not vote in the proposal, as `effective_votes`
+		/// counts them, recording their votes at the snapshot block.
+		fn record_delegators(who: &T::AccountId, proposal_id: u32, snapshot_block: T::BlockNumber, depth: u32) {
+			if depth >= T::MaxDelegationDepth::get() {
+				return;
+			}
+			for delegator in <Delegators<T>>::get(who).iter().filter(|d| !<VotedProposals<T>>::contains_key(proposal_id, *d)) {
+				<DelegatedVotes<T>>::insert(proposal_id, delegator, Self::votes_at(delegator, snapshot_block));
+				Self::record_delegators(delegator, proposal_id, snapshot_block, depth + 1);
+			}
+		}
+
+		/// Take the recorded votes of `who` and of its delegators that did not vote in the
+		/// proposal. These are the votes that a delegate ballot counted through `who`.
+		fn take_delegated_votes(who: &T::AccountId, proposal_id: u32, depth: u32) -> u128 {
+			let votes = <DelegatedVotes<T>>::take(proposal_id, who);
+			if depth >= T::MaxDelegationDepth::get() {
+				return votes;
+			}
+			<Delegators<T>>::get(who)
+				.iter()
+				.filter(|d| !<VotedProposals<T>>::contains_key(proposal_id, *d))
+				.fold(votes, |votes, d| votes.saturating_add(Self::take_delegated_votes(d, proposal_id, depth + 1)))
+		}
+
+		/// Reduce the power behind the ballot of `who` by `votes`, the split ballots are scaled
+		/// down in proportion.
+		fn reduce_cast_vote(who: &T::AccountId, proposal_id: u32, votes: u128) {
+			let mut active_proposal = match <ActiveProposals<T>>::get(proposal_id) {
+				Some(proposal) => proposal,
+				None => return,
+			};
+			let mut cast_vote = match <CastVotes<T>>::get(proposal_id, who) {
+				Some(cast_vote) => cast_vote,
+				None => return,
+			};
+			let new_power = cast_vote.power.saturating_sub(votes);
+			let removed = cast_vote.power - new_power;
+
+			let used_votes = match cast_vote.ballot {
+				Ballot::Split(ref mut split) => {
+					let mut used_votes = 0u128;
+					for vote in split.iter_mut() {
+						let new_votes = multiply_by_rational_with_rounding(vote.votes, new_power, cast_vote.power, Rounding::Down)
+							.unwrap_or_default();
+						for option in active_proposal.options.iter_mut() {
+							if option.id == vote.id {
+								option.votes = option.votes.saturating_sub(vote.votes - new_votes);
+							}
+						}
+						vote.votes = new_votes;
+						// the scaled votes add up to less than the checked votes of the ballot
+						used_votes = used_votes.saturating_add(new_votes);
+					}
+					used_votes
+				},
+				Ballot::Ranked(ref ranking) => {
+					if active_proposal.ballot_type == BallotType::Condorcet {
+						Self::update_pairwise(&active_proposal, ranking, removed, false);
+					} else {
+						<RankedBallots<T>>::mutate(proposal_id, |ballots| {
+							if let Some(ballot) = ballots.iter_mut().find(|b| b.ranking == *ranking) {
+								ballot.votes = ballot.votes.saturating_sub(removed);
+							}
+						});
+					}
+					for option in active_proposal.options.iter_mut() {
+						if option.id == ranking[0] {
+							option.votes = option.votes.saturating_sub(removed);
+						}
+					}
+					new_power
+				},
+				Ballot::Scored(ref scores) => {
+					for score in scores.iter() {
+						for option in active_proposal.options.iter_mut() {
+							if option.id == score.id {
+								option.votes = option.votes.saturating_sub(score.votes.saturating_mul(removed));
+							}
+						}
+					}
+					new_power
+				},
+			};
+
+			cast_vote.power = new_power;
+			Self::record_spent_votes(&mut active_proposal, who, used_votes);
+			<CastVotes<T>>::insert(proposal_id, who, cast_vote);
+			<ActiveProposals<T>>::insert(proposal_id, active_proposal);
+		}
+
+		/// Revoke the delegation of `who` and take its votes back from the open proposals.
+		fn do_undelegate(who: &T::AccountId) {
+			let delegate = match <Delegations<T>>::get(who) {
+				Some(delegate) => delegate,
+				None => return,
+			};
+			for proposal_id in <ActiveProposalIds<T>>::get() {
+				// only the votes counted in a delegate ballot are taken back
+				if !<VotedProposals<T>>::contains_key(proposal_id, who) {
+					let votes = Self::take_delegated_votes(who, proposal_id, 0);
+					Self::override_delegation(who, proposal_id, votes);
+				}
+			}
+			// the voter takes its own delegators away from every voter up the chain
+			let removed = <DelegatorCount<T>>::get(who).saturating_add(1);
+			let mut current = Some(delegate.clone());
+			let mut depth = 0u32;
+			while let Some(account) = current {
+				if depth >= T::MaxDelegationDepth::get() {
+					break;
+				}
+				<DelegatorCount<T>>::mutate(account.clone(), |count| *count = count.saturating_sub(removed));
+				current = <Delegations<T>>::get(account);
+				depth += 1;
+			}
+			<Delegations<T>>::remove(who);
+			<Delegators<T>>::mutate(delegate.clone(), |delegators| delegators.retain(|d| d != who));
+
+			Self::deposit_event(Event::Undelegated { who: who.clone(), from: delegate });
+		}
+
+		/// Check that `who` can reveal a ballot with the given hash and return the proposal with the
+		/// votes locked at commit time.
+		fn ensure_can_reveal(who: &T::AccountId, proposal_id: u32, ballot_hash: T::Hash) -> Result<(CurrentProposal<T>, u128), DispatchError> {
+			let active_proposal = <ActiveProposals<T>>::get(proposal_id).ok_or(Error::<T>::NoActiveProposal)?;
+			let reveal_end_block = active_proposal.reveal_end_block.ok_or(Error::<T>::NotASecretProposal)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(active_proposal.end_block < now && now <= reveal_end_block, Error::<T>::NotInRevealPhase);
+
+			let commitment = <Commitments<T>>::get(who, proposal_id).ok_or(Error::<T>::NoCommitment)?;
+			ensure!(commitment == ballot_hash, Error::<T>::InvalidReveal);
+
+			Ok((active_proposal, <VotedProposals<T>>::get(proposal_id, who).unwrap_or_default()))
+		}
+
+		/// Last block in which the proposal accepts ballots, including the reveal phase.
+		fn closing_block(proposal: &CurrentProposal<T>) -> T::BlockNumber {
+			proposal.reveal_end_block.unwrap_or(proposal.end_block)
+		}
+
+		/// Votes given by the stake reserved by `who` through the vote power curve.
+		fn compute_votes(who: &T::AccountId) -> u128 {
+			let reserves_to_compute = T::Currency::reserved_balance_named(&T::VotingReserveId::get(), who);
+			T::VotePowerCurve::votes(reserves_to_compute.try_into().unwrap_or(u128::MAX))
+		}
+
+		/// Check that `who` can vote in the proposal and return the proposal with the available votes.
+		fn ensure_can_vote(who: &T::AccountId, proposal_id: u32) -> Result<(CurrentProposal<T>, u128), DispatchError> {
+			// check if the voter is registered
+			ensure!(<Voters<T>>::contains_key(who), Error::<T>::NotAVoter);
+			// get the active proposal
+			let active_proposal = <ActiveProposals<T>>::get(proposal_id).ok_or(Error::<T>::NoActiveProposal)?;
+			let available_votes = Self::available_votes(who, &active_proposal);
+			// check if the voter has mroe than 0 votes
+			ensure!(!available_votes.is_zero(), Error::<T>::NotEnoughVotes);
+
+			// check if the proposal is open
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(active_proposal.start_block <= now, Error::<T>::ProposalNotStarted);
+			ensure!(active_proposal.end_block >= now, Error::<T>::ProposalFinished);
+
+			// check if the voter has already voted
+			ensure!(!<VotedProposals<T>>::contains_key(proposal_id, who), Error::<T>::AlreadyVoted);
+
+			Ok((active_proposal, available_votes))
+		}
+
+		/// Votes of a ballot cast with `conviction`: the own votes of the voter are multiplied and
+		/// the delegated votes are counted once. The conviction lock of the voter stake is extended
+		/// to cover the conviction, the stake stays reserved and it is only locked in the currency
+		/// once it is unreserved.
+		fn apply_conviction(who: &T::AccountId, proposal: &CurrentProposal<T>, available_votes: u128, conviction: Conviction) -> Result<u128, DispatchError> {
+			// the asset proposals have no delegated votes and no lock on the native stake
+			if <ProposalAssets<T>>::contains_key(proposal.id) {
+				ensure!(conviction == Conviction::None, Error::<T>::ConvictionNotAllowed);
+				return Ok(available_votes);
+			}
+			let own_votes = Self::votes_at(who, proposal.snapshot_block).min(available_votes);
+			let delegated_votes = available_votes - own_votes;
+
+			let periods = conviction.lock_periods();
+			if periods > 0 {
+				let until = Self::closing_block(proposal)
+					.saturating_add(T::VoteLockingPeriod::get().saturating_mul(periods.into()));
+				let amount = T::Currency::reserved_balance_named(&T::VotingReserveId::get(), who);
+				// a later vote only extends the lock, in amount and in time
+				<VoteLocks<T>>::mutate(who, |lock| {
+					let (locked, locked_until) = lock.unwrap_or_default();
+					*lock = Some((locked.max(amount), locked_until.max(until)));
+				});
+			}
+
+			Ok(own_votes.saturating_mul(conviction.multiplier()).saturating_add(delegated_votes))
+		}
+
+		/// Votes of `who` in the proposal, given by the stake at the snapshot block. In an asset
+		/// proposal they are given by the tokens of the asset held at the snapshot block, but no
+		/// more than the ones still held: the tokens behind a ballot stay held until the proposal is
+		/// closed, so they back a single ballot per proposal. The delegated votes are only used in
+		/// the public proposals without an asset.
+		fn available_votes(who: &T::AccountId, proposal: &CurrentProposal<T>) -> u128 {
+			if let Some(asset) = <ProposalAssets<T>>::get(proposal.id) {
+				Self::asset_votes_at(who, asset, proposal.snapshot_block).min(Self::asset_votes(who, asset))
+			} else if proposal.reveal_end_block.is_some() {
+				Self::votes_at(who, proposal.snapshot_block)
+			} else {
+				Self::effective_votes(who, Some((proposal.id, proposal.snapshot_block)), 0)
+			}
+		}
+
+		/// Votes given by the tokens of `asset` held by `who` through the vote power curve.
+		fn asset_votes(who: &T::AccountId, asset: AssetIdOf<T>) -> u128 {
+			let stake = <AssetStakes<T>>::get(who, asset);
+			T::VotePowerCurve::votes(stake.try_into().unwrap_or(u128::MAX))
+		}
+
+		/// Check that the voter has voted, or retracted its ballot, in an open public proposal.
+		fn ensure_can_change_ballot(who: &T::AccountId, proposal_id: u32) -> Result<CurrentProposal<T>, DispatchError> {
+			ensure!(<Voters<T>>::contains_key(who), Error::<T>::NotAVoter);
+			let active_proposal = <ActiveProposals<T>>::get(proposal_id).ok_or(Error::<T>::NoActiveProposal)?;
+			ensure!(active_proposal.end_block >= <frame_system::Pallet<T>>::block_number(), Error::<T>::ProposalFinished);
+			ensure!(active_proposal.reveal_end_block.is_none(), Error::<T>::SecretBallotRequired);
+			ensure!(<VotedProposals<T>>::contains_key(proposal_id, who), Error::<T>::NotVoted);
+			// the ballots migrated from the version 0 of the storage have votes but no `CastVotes`
+			ensure!(
+				<CastVotes<T>>::contains_key(proposal_id, who) || !Self::has_voted(who.clone(), proposal_id),
+				Error::<T>::BallotNotStored
+			);
+			Ok(active_proposal)
+		}
+
+		/// Remove the ballot of `who` from the tallies of the proposal. The voter stays marked in
+		/// `VotedProposals`, with no votes, so the delegates keep leaving its votes out. Returns
+		/// whether there was a ballot to remove.
+		fn remove_ballot(who: &T::AccountId, active_proposal: &mut CurrentProposal<T>) -> bool {
+			let cast_vote = match <CastVotes<T>>::take(active_proposal.id, who) {
+				Some(cast_vote) => cast_vote,
+				None => return false,
+			};
+
+			match cast_vote.ballot {
+				Ballot::Split(split) => {
+					for vote in split.iter() {
+						for option in active_proposal.options.iter_mut() {
+							if option.id == vote.id {
+								option.votes = option.votes.saturating_sub(vote.votes);
+							}
+						}
+					}
+				},
+				Ballot::Ranked(ranking) => {
+					if active_proposal.ballot_type == BallotType::Condorcet {
+						Self::update_pairwise(active_proposal, &ranking, cast_vote.power, false);
+					} else {
+						<RankedBallots<T>>::mutate(active_proposal.id, |ballots| {
+							if let Some(ballot) = ballots.iter_mut().find(|b| b.ranking == ranking) {
+								ballot.votes = ballot.votes.saturating_sub(cast_vote.power);
+							}
+							ballots.retain(|b| !b.votes.is_zero());
+						});
+					}
+					for option in active_proposal.options.iter_mut() {
+						if option.id == ranking[0] {
+							option.votes = option.votes.saturating_sub(cast_vote.power);
+						}
+					}
+				},
+				Ballot::Scored(scores) => {
+					for score in scores.iter() {
+						for option in active_proposal.options.iter_mut() {
+							if option.id == score.id {
+								option.votes = option.votes.saturating_sub(score.votes.saturating_mul(cast_vote.power));
+							}
+						}
+					}
+				},
+			}
+
+			active_proposal.voters = active_proposal.voters.saturating_sub(1);
+			Self::record_spent_votes(active_proposal, who, 0);
+			true
+		}
+
+		/// Check that the voter has nothing pending in the proposals and can leave.
+		fn ensure_can_withdraw(who: &T::AccountId) -> DispatchResult {
+			// check that the voter has no votes locked in an open proposal
+			ensure!(
+				!<ActiveProposalIds<T>>::get().iter().any(|id| Self::has_voted(who.clone(), *id)),
+				Error::<T>::VotesLocked
+			);
+			// check that the voter has no unrevealed ballots waiting to be slashed
+			ensure!(!Self::has_pending_reveal(who), Error::<T>::VotesLocked);
+			// check that the voter has no deposits of queued proposals
+			ensure!(
+				T::Currency::reserved_balance_named(&T::DepositReserveId::get(), who).is_zero(),
+				Error::<T>::VotesLocked
+			);
+
+			Ok(())
+		}
+
+		/// Whether `who` has committed ballots that are not revealed yet or can still be slashed.
+		/// The ballots committed to cancelled proposals are never slashed.
+		fn has_pending_reveal(who: &T::AccountId) -> bool {
+			<Commitments<T>>::iter_key_prefix(who.clone()).any(|proposal_id| {
+				<FinishedProposals<T>>::get(proposal_id).map_or(true, |p| p.status != ProposalStatus::Cancelled)
+			})
+		}
+
+		/// Remove the voter and free the stake and the register fee reserved before the fees funded
+		/// the reward pot.
+		fn do_withdraw(who: &T::AccountId) {
+			// revoke the delegations from and to the voter
+			if <Delegations<T>>::contains_key(who.clone()) {
+				Self::do_undelegate(who);
+			}
+			for delegator in <Delegators<T>>::take(who.clone()) {
+				<Delegations<T>>::remove(delegator);
+			}
+			<DelegatorCount<T>>::remove(who.clone());
+
+			// kill the voters storage
+			Self::checkpoint_power(who, 0);
+			<Voters<T>>::remove(who.clone());
+			<VoterSince<T>>::remove(who.clone());
+
+			// free the stake and the register fee, the reserves of other pallets are not touched
+			T::Currency::unreserve_all_named(&T::VotingReserveId::get(), who);
+			T::Currency::unreserve_all_named(&T::RegisterReserveId::get(), who);
+			// the stake locked by conviction votes stays locked until the lock expires
+			Self::lock_unreserved_stake(who);
+		}
+
+		/// Lock in the currency the stake under an unexpired conviction lock that is not reserved
+		/// anymore. The stake still reserved is not locked again, so the free balance of the voter
+		/// stays spendable while the stake is bonded.
+		fn lock_unreserved_stake(who: &T::AccountId) {
+			let (locked, until) = match <VoteLocks<T>>::get(who) {
+				Some(lock) => lock,
+				None => return,
+			};
+			if until < <frame_system::Pallet<T>>::block_number() {
+				T::Currency::remove_lock(VOTING_ID, who);
+				<VoteLocks<T>>::remove(who);
+				return;
+			}
+			let reserved = T::Currency::reserved_balance_named(&T::VotingReserveId::get(), who);
+			let unreserved = locked.saturating_sub(reserved);
+			if unreserved.is_zero() {
+				T::Currency::remove_lock(VOTING_ID, who);
+			} else {
+				T::Currency::set_lock(VOTING_ID, who, unreserved, WithdrawReasons::all());
+			}
+		}
+
+		/// Whether the voter skipped more consecutive finished proposals than
+		/// `MaxMissedProposals`. Only the proposals the voter could take part in are missed: the
+		/// proposals created before the voter registered, the asset proposals and the proposals
+		/// in which the voter had no power at the snapshot block break the streak. A voter that
+		/// delegates its votes is never inactive.
+		pub fn is_inactive(who: &T::AccountId) -> bool {
+			let recent = <RecentProposals<T>>::get();
+			let since = <VoterSince<T>>::get(who);
+			recent.len() as u32 == MaxRecentProposals::<T>::get() &&
+				!<Delegations<T>>::contains_key(who) &&
+				recent.iter().all(|(id, snapshot_block)| {
+					*id >= since &&
+						!<ProposalAssets<T>>::contains_key(id) &&
+						!Self::votes_at(who, *snapshot_block).is_zero() &&
+						!Self::has_voted(who.clone(), *id)
+				})
+		}
+
+		/// Record a finished proposal, dropping the oldest one when the list is full.
+		fn record_recent_proposal(proposal_id: u32, snapshot_block: T::BlockNumber) {
+			<RecentProposals<T>>::mutate(|recent| {
+				if recent.len() as u32 >= MaxRecentProposals::<T>::get() {
+					recent.remove(0);
+				}
+				let _ = recent.try_push((proposal_id, snapshot_block));
+			});
+		}
+
+		/// Record the votes spent by `who` in the proposal and update the total of the proposal.
+		fn record_spent_votes(proposal: &mut CurrentProposal<T>, who: &T::AccountId, votes: u128) {
+			let previous = <VotedProposals<T>>::get(proposal.id, who).unwrap_or_default();
+			proposal.spent_votes = proposal.spent_votes.saturating_sub(previous).saturating_add(votes);
+			<VotedProposals<T>>::insert(proposal.id, who, votes);
+		}
+
+		/// Account of the reward pot.
+		pub fn reward_pot() -> T::AccountId {
+			T::PalletId::get().into_account_truncating()
+		}
+
+		/// Weight of ending `proposal`, by its number of options and of distinct rankings.
+		fn end_proposal_weight(proposal: &CurrentProposal<T>) -> Weight {
+			let rankings = <RankedBallots<T>>::decode_len(proposal.id).unwrap_or_default() as u32;
+			<T::WeightInfo as weights::WeightInfo>::end_proposal(proposal.options.len() as u32, rankings)
+		}
+
+		/// Finalize up to `max` proposals whose end block is before `now`, while their weight fits
+		/// in `max_weight`. Returns the weight consumed.
+		fn finalize_expired(now: T::BlockNumber, max: u32, max_weight: Weight) -> Weight {
+			let ids = <ActiveProposalIds<T>>::get();
+			let mut weight = T::DbWeight::get().reads(1);
+			let mut finalized = 0u32;
+
+			for id in ids.iter() {
+				if finalized >= max {
+					break;
+				}
+				weight = weight.saturating_add(T::DbWeight::get().reads(1));
+				if let Some(proposal) = <ActiveProposals<T>>::get(id) {
+					if Self::closing_block(&proposal) < now {
+						weight = weight.saturating_add(T::DbWeight::get().reads(1));
+						let proposal_weight = Self::end_proposal_weight(&proposal);
+						if weight.saturating_add(proposal_weight).any_gt(max_weight) {
+							break;
+						}
+						Self::do_end_proposal(proposal);
+						finalized += 1;
+						weight = weight.saturating_add(proposal_weight);
+					}
+				}
+			}
+			weight
+		}
+
+		/// Remove the ballots of the closed proposals, up to `MaxBallotRemovalsPerBlock` entries
+		/// that fit in `max_weight`. The proposals whose ballots do not fit are carried on in the
+		/// next blocks, starting over the entries left. Returns the weight consumed.
+		fn clear_closed_ballots(max_weight: Weight) -> Weight {
+			let db_weight = T::DbWeight::get();
+			// every entry removed is read and written
+			let entry_weight = db_weight.reads_writes(1, 1);
+			let mut weight = Weight::zero();
+			let mut budget = T::MaxBallotRemovalsPerBlock::get();
+
+			loop {
+				weight = weight.saturating_add(db_weight.reads(1));
+				let fitting = max_weight
+					.saturating_sub(weight)
+					.ref_time()
+					.checked_div(entry_weight.ref_time())
+					.unwrap_or(u64::MAX);
+				let limit = budget.min(fitting.saturated_into());
+				if limit.is_zero() || weight.any_gt(max_weight) {
+					break;
+				}
+				let (proposal_id, cancelled) = match <ClosedBallots<T>>::iter().next() {
+					Some(closed) => closed,
+					None => break,
+				};
+
+				let (removed, done) = Self::clear_ballots(proposal_id, cancelled, limit);
+				weight = weight.saturating_add(entry_weight.saturating_mul(removed.into()));
+				budget = budget.saturating_sub(removed);
+				if !done {
+					break;
+				}
+				<ClosedBallots<T>>::remove(proposal_id);
+				weight = weight.saturating_add(db_weight.writes(1));
+			}
+			weight
+		}
+
+		/// Remove up to `limit` ballot entries of a closed proposal, and of a cancelled one the
+		/// commitments and the `VotedProposals` entries of its voters. Returns the number of entries
+		/// removed and whether none is left.
+		fn clear_ballots(proposal_id: u32, cancelled: bool, limit: u32) -> (u32, bool) {
+			let cast_votes = <CastVotes<T>>::clear_prefix(proposal_id, limit, None);
+			let mut removed = cast_votes.unique;
+			if cast_votes.maybe_cursor.is_some() || removed >= limit {
+				return (removed, false);
+			}
+			let delegated_votes = <DelegatedVotes<T>>::clear_prefix(proposal_id, limit - removed, None);
+			removed = removed.saturating_add(delegated_votes.unique);
+			if delegated_votes.maybe_cursor.is_some() || !cancelled {
+				return (removed, delegated_votes.maybe_cursor.is_none());
+			}
+
+			// the committed ballots are marked in the voted proposals too, a voter takes two entries
+			let voters: Vec<T::AccountId> = <VotedProposals<T>>::iter_key_prefix(proposal_id)
+				.take((limit.saturating_sub(removed) / 2) as usize + 1)
+				.collect();
+			for who in voters.iter() {
+				if removed.saturating_add(2) > limit {
+					return (removed, false);
+				}
+				<VotedProposals<T>>::remove(proposal_id, who);
+				<Commitments<T>>::remove(who, proposal_id);
+				removed = removed.saturating_add(2);
+			}
+			(removed, true)
+		}
+
+		/// Get the votes of a voter plus all the votes delegated to it.
+		pub fn get_effective_vote_amount(who: T::AccountId) -> Option<u128> {
+			if !Voters::<T>::contains_key(who.clone()) {
+				return None;
+			}
+			Some(Self::effective_votes(&who, None, 0))
+		}
+
+		/// Get the ids of the open proposals.
+		pub fn get_active_proposal_ids() -> Vec<u32> {
+			ActiveProposalIds::<T>::get().into_inner()
+		}
+
+		pub fn get_closed_proposal(id: u32) -> Option<FinishedProposal<T>> {
+			FinishedProposals::<T>::get(id)
+		}
+
+		/// Get up to `count` finished proposals, starting from the proposal id `start`.
+		pub fn get_closed_proposals(start: u32, count: u32) -> Vec<FinishedProposal<T>> {
+			(start..<ProposalCount<T>>::get())
+				.filter_map(FinishedProposals::<T>::get)
+				.take(count as usize)
+				.collect()
+		}
+
+		/// Whether the account has votes in a ballot, or a committed ballot, in the proposal.
+		pub fn has_voted(who: T::AccountId, proposal_id: u32) -> bool {
+			<VotedProposals<T>>::get(proposal_id, who).map_or(false, |votes| !votes.is_zero())
 		}
 	}
 }
\ No newline at end of file
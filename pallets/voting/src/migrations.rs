@@ -0,0 +1,200 @@
+//! Storage migrations of the pallet.
+
+use crate::*;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{NamedReservableCurrency, OnRuntimeUpgrade, ReservableCurrency, StorageVersion},
+};
+use sp_runtime::traits::{One, SaturatedConversion, Saturating, Zero};
+use sp_std::{vec, vec::Vec};
+
+/// Migration to the version 1 of the storage.
+pub mod v1 {
+	use super::*;
+
+	/// The storage items of the version 0 replaced in the version 1.
+	pub(crate) mod v0 {
+		use super::*;
+
+		#[derive(Encode, Decode)]
+		pub struct CurrentProposal<T: Config> {
+			pub id: u32,
+			pub end_block: T::BlockNumber,
+			pub status: ProposalStatus,
+			pub text: T::Hash,
+			pub options: BoundedVec<Options<T::Hash>, T::MaxVecLen>,
+		}
+
+		#[derive(Encode, Decode)]
+		pub struct FinishedProposal<T: Config> {
+			pub id: u32,
+			pub text: T::Hash,
+			pub end_block: T::BlockNumber,
+			pub status: ProposalStatus,
+			pub options_votes: BoundedVec<Options<T::Hash>, T::MaxVecLen>,
+			pub winner_index: u8,
+		}
+
+		#[frame_support::storage_alias]
+		pub type ActiveProposal<T: Config> = StorageValue<Pallet<T>, CurrentProposal<T>>;
+
+		/// Last proposal voted by an account.
+		#[frame_support::storage_alias]
+		pub type VotedProposals<T: Config> =
+			StorageMap<Pallet<T>, Blake2_128, <T as frame_system::Config>::AccountId, u32, ValueQuery>;
+
+		#[frame_support::storage_alias]
+		pub type FinishedProposals<T: Config> = StorageMap<Pallet<T>, Blake2_128, u32, FinishedProposal<T>>;
+	}
+
+	/// Move the single open proposal of the version 0 to `ActiveProposals` and key the
+	/// `VotedProposals` entries of its voters by the proposal too, the other entries are removed.
+	/// The ballots of the version 0 were not stored, so the current votes of a voter are taken as
+	/// the votes it spent and its ballot cannot be changed. The voters are keyed by an opaque hash,
+	/// so they are found among the accounts of the system pallet, which are only walked when there
+	/// is an open proposal. The finished proposals, found by their ids, get the fields of the
+	/// version 1 empty, as split proposals.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() >= 1 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let old = v0::ActiveProposal::<T>::take();
+			let mut reads = 3u64;
+			let mut voters = Vec::new();
+			if let Some(old) = &old {
+				for who in frame_system::Account::<T>::iter_keys() {
+					reads += 2;
+					if v0::VotedProposals::<T>::get(&who) == old.id {
+						voters.push(who);
+					}
+				}
+			}
+			let removed = v0::VotedProposals::<T>::clear(u32::MAX, None).unique as u64;
+			let mut writes = 2 + removed;
+
+			if let Some(old) = old {
+				let start_block = old.end_block.saturating_sub(T::MaxProposalDuration::get());
+				let mut proposal = CurrentProposal::<T> {
+					id: old.id,
+					snapshot_block: start_block.saturating_sub(One::one()),
+					start_block,
+					end_block: old.end_block,
+					reveal_end_block: None,
+					status: old.status,
+					text: old.text,
+					ballot_type: BallotType::Split,
+					options: old.options,
+					thresholds: Thresholds::default(),
+					tie_break: T::DefaultTieBreak::get(),
+					voters: 0,
+					spent_votes: 0,
+				};
+				for who in voters {
+					let votes = Voters::<T>::get(&who).unwrap_or_default();
+					VotedProposals::<T>::insert(proposal.id, &who, votes);
+					proposal.voters = proposal.voters.saturating_add(1);
+					proposal.spent_votes = proposal.spent_votes.saturating_add(votes);
+					reads += 1;
+					writes += 1;
+				}
+				ActiveProposalIds::<T>::put(BoundedVec::truncate_from(vec![proposal.id]));
+				ActiveProposals::<T>::insert(proposal.id, proposal);
+				writes += 2;
+			}
+
+			for id in 1..ProposalCount::<T>::get() {
+				reads += 1;
+				let old = match v0::FinishedProposals::<T>::get(id) {
+					Some(old) => old,
+					None => continue,
+				};
+				FinishedProposals::<T>::insert(id, FinishedProposal::<T> {
+					id: old.id,
+					text: old.text,
+					end_block: old.end_block,
+					status: old.status,
+					ballot_type: BallotType::Split,
+					options_votes: old.options_votes,
+					winner_index: old.winner_index,
+					rounds: BoundedVec::default(),
+					tie_break: T::DefaultTieBreak::get(),
+					tied: BoundedVec::default(),
+					ranking: BoundedVec::default(),
+					pairwise: BoundedVec::default(),
+					winners: BoundedVec::default(),
+				});
+				writes += 1;
+			}
+			StorageVersion::new(1).put::<Pallet<T>>();
+
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+	}
+}
+
+/// Migration to the version 2 of the storage.
+pub mod v2 {
+	use super::*;
+
+	/// Move the tokens that the voters reserved before the named reserves existed into them: the
+	/// deposits of the queued proposals to `DepositReserveId`, the register fee to
+	/// `RegisterReserveId` and the stake recorded by the votes to `VotingReserveId`. The old votes
+	/// were the square root of the stake, so the stake is taken as the square of the votes. The
+	/// rest of the anonymous reserve, the reserves of other pallets, is not touched.
+	pub struct MoveToNamedReserves<T>(PhantomData<T>);
+
+	impl<T: Config> MoveToNamedReserves<T> {
+		/// Move up to `amount` of the anonymous reserve of `who` to the named reserve `id`, returns
+		/// the amount moved.
+		fn move_reserve(id: &ReserveIdOf<T>, who: &T::AccountId, amount: BalanceOf<T>) -> BalanceOf<T> {
+			let amount = amount.min(T::Currency::reserved_balance(who));
+			if amount.is_zero() {
+				return amount;
+			}
+			T::Currency::unreserve(who, amount);
+			// the tokens were reserved a moment ago, so they can be reserved again
+			let _ = T::Currency::reserve_named(id, who, amount);
+			amount
+		}
+	}
+
+	impl<T: Config> OnRuntimeUpgrade for MoveToNamedReserves<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() >= 2 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let queue = ProposalQueue::<T>::get();
+			let mut accounts = 0u64;
+			let mut moved = 0u64;
+			for who in frame_system::Account::<T>::iter_keys() {
+				accounts += 1;
+				if !Voters::<T>::contains_key(&who) {
+					continue;
+				}
+				let named = [T::VotingReserveId::get(), T::RegisterReserveId::get(), T::DepositReserveId::get(), T::UnbondingReserveId::get()]
+					.iter()
+					.fold(BalanceOf::<T>::zero(), |total, id| total.saturating_add(T::Currency::reserved_balance_named(id, &who)));
+				let mut anonymous = T::Currency::reserved_balance(&who).saturating_sub(named);
+
+				let deposits = queue
+					.iter()
+					.filter(|pending| pending.proposer == who || pending.seconds.contains(&who))
+					.fold(BalanceOf::<T>::zero(), |total, pending| total.saturating_add(pending.deposit));
+				anonymous.saturating_reduce(Self::move_reserve(&T::DepositReserveId::get(), &who, deposits.min(anonymous)));
+				anonymous.saturating_reduce(Self::move_reserve(&T::RegisterReserveId::get(), &who, T::RegisterFee::get().min(anonymous)));
+				let votes = Voters::<T>::get(&who).unwrap_or_default();
+				let stake = votes.saturating_mul(votes).saturated_into::<BalanceOf<T>>();
+				Self::move_reserve(&T::VotingReserveId::get(), &who, stake.min(anonymous));
+				moved += 1;
+			}
+			StorageVersion::new(2).put::<Pallet<T>>();
+
+			T::DbWeight::get().reads_writes(2 + 2 * accounts + moved, 1 + 3 * moved)
+		}
+	}
+}
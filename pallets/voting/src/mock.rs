@@ -1,12 +1,23 @@
 use crate as pallet_voting;
+use crate::{curve::SquareRoot, TieBreak};
+use codec::Encode;
 use frame_support::{
+	dispatch::DispatchResult,
 	parameter_types,
-	traits::{ ConstU16, ConstU32, ConstU64},
+	storage::unhashed,
+	traits::{
+		tokens::{fungibles, DepositConsequence, WithdrawConsequence},
+		ConstU128, ConstU16, ConstU32, ConstU64,
+	},
+	weights::Weight,
+	PalletId,
 };
+use frame_system::EnsureRoot;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
+	DispatchError, Perbill, TokenError,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -22,6 +33,8 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system,
 		Balances: pallet_balances,
+		Preimage: pallet_preimage,
+		RandomnessCollectiveFlip: pallet_randomness_collective_flip,
 		Voting: pallet_voting,
 	}
 );
@@ -57,6 +70,16 @@ parameter_types! {
 	pub const ExistentialDeposit: u64 = 1;
 	pub const MaxLocks: u32 = 10;
 	pub const RegisterFeeValue: u128 = 50;
+	pub const UnrevealedSlashValue: Perbill = Perbill::from_percent(10);
+	pub const InactivitySlashValue: Perbill = Perbill::from_percent(20);
+	pub const DefaultTieBreakValue: TieBreak = TieBreak::Declare;
+	pub const VotingReserveIdValue: [u8; 8] = *b"vote/stk";
+	pub const RegisterReserveIdValue: [u8; 8] = *b"vote/fee";
+	pub const DepositReserveIdValue: [u8; 8] = *b"vote/dep";
+	pub const UnbondingReserveIdValue: [u8; 8] = *b"vote/unb";
+	pub const PalletIdValue: PalletId = PalletId(*b"py/votng");
+	pub static MaxEnactmentWeightValue: Weight = Weight::MAX;
+	pub static MaxProposalDurationValue: u64 = 10;
 }
 impl pallet_balances::Config for Test {
 	type Balance = u128;
@@ -66,16 +89,194 @@ impl pallet_balances::Config for Test {
 	type AccountStore = System;
 	type WeightInfo = ();
 	type MaxLocks = MaxLocks;
-	type MaxReserves = ();
+	type MaxReserves = ConstU32<10>;
 	type ReserveIdentifier = [u8; 8];
 }
 
+impl pallet_preimage::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRoot<u64>;
+	type BaseDeposit = ConstU128<1>;
+	type ByteDeposit = ConstU128<1>;
+}
+
+impl pallet_randomness_collective_flip::Config for Test {}
+
+/// The only asset of the mock runtime.
+pub const GOVERNANCE_ASSET: u32 = 1;
+
+/// Assets kept in the test externalities: the free and held tokens of every account.
+pub struct MockAssets;
+
+impl MockAssets {
+	fn key(asset: u32, who: &u64) -> Vec<u8> {
+		(b"mock/assets", asset, who).encode()
+	}
+
+	fn get(asset: u32, who: &u64) -> (Balance, Balance) {
+		unhashed::get_or_default(&Self::key(asset, who))
+	}
+
+	fn put(asset: u32, who: &u64, free: Balance, held: Balance) {
+		unhashed::put(&Self::key(asset, who), &(free, held));
+	}
+
+	/// Set the free tokens of an account.
+	pub fn set_balance(asset: u32, who: &u64, amount: Balance) {
+		let (_, held) = Self::get(asset, who);
+		Self::put(asset, who, amount, held);
+	}
+
+	/// Free and held tokens of an account.
+	pub fn balances(asset: u32, who: &u64) -> (Balance, Balance) {
+		Self::get(asset, who)
+	}
+}
+
+impl fungibles::Inspect<u64> for MockAssets {
+	type AssetId = u32;
+	type Balance = Balance;
+
+	fn total_issuance(_: u32) -> Balance {
+		0
+	}
+
+	fn minimum_balance(_: u32) -> Balance {
+		0
+	}
+
+	fn balance(asset: u32, who: &u64) -> Balance {
+		let (free, held) = Self::get(asset, who);
+		free + held
+	}
+
+	fn reducible_balance(asset: u32, who: &u64, _: bool) -> Balance {
+		Self::get(asset, who).0
+	}
+
+	fn can_deposit(_: u32, _: &u64, _: Balance, _: bool) -> DepositConsequence {
+		DepositConsequence::Success
+	}
+
+	fn can_withdraw(asset: u32, who: &u64, amount: Balance) -> WithdrawConsequence<Balance> {
+		if Self::get(asset, who).0 >= amount { WithdrawConsequence::Success } else { WithdrawConsequence::NoFunds }
+	}
+
+	fn asset_exists(asset: u32) -> bool {
+		asset == GOVERNANCE_ASSET
+	}
+}
+
+impl fungibles::InspectHold<u64> for MockAssets {
+	fn balance_on_hold(asset: u32, who: &u64) -> Balance {
+		Self::get(asset, who).1
+	}
+
+	fn can_hold(asset: u32, who: &u64, amount: Balance) -> bool {
+		Self::get(asset, who).0 >= amount
+	}
+}
+
+impl fungibles::Transfer<u64> for MockAssets {
+	fn transfer(asset: u32, source: &u64, dest: &u64, amount: Balance, _: bool) -> Result<Balance, DispatchError> {
+		let (free, held) = Self::get(asset, source);
+		let free = free.checked_sub(amount).ok_or(TokenError::NoFunds)?;
+		Self::put(asset, source, free, held);
+		let (dest_free, dest_held) = Self::get(asset, dest);
+		Self::put(asset, dest, dest_free + amount, dest_held);
+		Ok(amount)
+	}
+}
+
+impl fungibles::MutateHold<u64> for MockAssets {
+	fn hold(asset: u32, who: &u64, amount: Balance) -> DispatchResult {
+		let (free, held) = Self::get(asset, who);
+		let free = free.checked_sub(amount).ok_or(TokenError::NoFunds)?;
+		Self::put(asset, who, free, held + amount);
+		Ok(())
+	}
+
+	fn release(asset: u32, who: &u64, amount: Balance, best_effort: bool) -> Result<Balance, DispatchError> {
+		let (free, held) = Self::get(asset, who);
+		let amount = if best_effort { amount.min(held) } else { amount };
+		let held = held.checked_sub(amount).ok_or(TokenError::NoFunds)?;
+		Self::put(asset, who, free + amount, held);
+		Ok(amount)
+	}
+
+	fn transfer_held(asset: u32, source: &u64, dest: &u64, amount: Balance, best_effort: bool, on_hold: bool) -> Result<Balance, DispatchError> {
+		let (free, held) = Self::get(asset, source);
+		let amount = if best_effort { amount.min(held) } else { amount };
+		let held = held.checked_sub(amount).ok_or(TokenError::NoFunds)?;
+		Self::put(asset, source, free, held);
+		let (dest_free, dest_held) = Self::get(asset, dest);
+		if on_hold {
+			Self::put(asset, dest, dest_free, dest_held + amount);
+		} else {
+			Self::put(asset, dest, dest_free + amount, dest_held);
+		}
+		Ok(amount)
+	}
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl crate::assets::BenchmarkHelper<u32, u64, Balance> for MockAssets {
+	fn funded_asset(who: &u64, amount: Balance) -> Option<u32> {
+		Self::set_balance(GOVERNANCE_ASSET, who, amount);
+		Some(GOVERNANCE_ASSET)
+	}
+}
+
 impl pallet_voting::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Preimages = Preimage;
 	type Currency = Balances;
+	type VotingReserveId = VotingReserveIdValue;
+	type RegisterReserveId = RegisterReserveIdValue;
+	type DepositReserveId = DepositReserveIdValue;
+	type UnbondingReserveId = UnbondingReserveIdValue;
 	type RegisterFee = RegisterFeeValue;
+	type VotePowerCurve = SquareRoot;
 	type MaxVecLen = ConstU32<3>;
-	type MaxProposalDuration = ConstU64<10>;
+	type MinProposalDuration = ConstU64<2>;
+	type MaxProposalDuration = MaxProposalDurationValue;
+	type MaxStartDelay = ConstU64<50>;
+	type MaxActiveProposals = ConstU32<2>;
+	type MaxFinalizationsPerBlock = ConstU32<1>;
+	type MaxBallotRemovalsPerBlock = ConstU32<2>;
+	type MaxRankings = ConstU32<4>;
+	type RevealPeriod = ConstU64<5>;
+	type UnrevealedSlash = UnrevealedSlashValue;
+	type Randomness = RandomnessCollectiveFlip;
+	type TieBreakOrigin = EnsureRoot<u64>;
+	type CancelOrigin = EnsureRoot<u64>;
+	type VetoOrigin = EnsureRoot<u64>;
+	type CooldownPeriod = ConstU64<20>;
+	type DefaultTieBreak = DefaultTieBreakValue;
+	type EnactmentDelay = ConstU64<2>;
+	type MaxEnactmentWeight = MaxEnactmentWeightValue;
+	type VoteLockingPeriod = ConstU64<5>;
+	type MaxDelegationDepth = ConstU32<2>;
+	type MaxDelegators = ConstU32<2>;
+	type MaxTotalDelegators = ConstU32<3>;
+	type ProposalDeposit = ConstU128<20>;
+	type MaxQueuedProposals = ConstU32<2>;
+	type LaunchPeriod = ConstU64<4>;
+	type MaxSeconds = ConstU32<2>;
+	type UnbondingPeriod = ConstU64<5>;
+	type MaxUnlockingChunks = ConstU32<2>;
+	type PalletId = PalletIdValue;
+	type ProposalReward = ConstU128<30>;
+	type MaxMissedProposals = ConstU32<1>;
+	type InactivitySlash = InactivitySlashValue;
+	type InactivitySlashHandler = pallet_voting::RewardPot<Test>;
+	type Assets = MockAssets;
+	#[cfg(feature = "runtime-benchmarks")]
+	type AssetBenchmarkHelper = MockAssets;
 	type WeightInfo = ();
 }
 
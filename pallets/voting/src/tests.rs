@@ -1,15 +1,26 @@
-use crate::{mock::*, Error, Event, Options, VoteStruct};
+use crate::curve::{CappedLinear, Curve, Linear, Logarithmic, SquareRoot, VotePowerCurve};
+use crate::{
+	mock::*, BallotType, CastVotes, Commitments, Conviction, Enactments, Error, Event, OptionCalls, Options, PairwiseMatrices, ProposalStatus,
+	RejectionReason, RunoffRound, Thresholds, TieBreak, Unlocking, VoteLocks, VoteStruct, VotedProposals,
+};
 use frame_support::{assert_noop, assert_ok};
 use frame_support::bounded_vec;
-use frame_support::pallet_prelude::ConstU32;
+use frame_support::pallet_prelude::{ConstU128, ConstU32, Get};
+use frame_support::traits::{Currency, Hooks, NamedReservableCurrency, ReservableCurrency, StorePreimage};
+use frame_support::weights::Weight;
 
-use sp_runtime::BoundedVec;
+use sp_runtime::{ArithmeticError, BoundedVec, DispatchError, Perbill, TokenError};
 
 type BalanceError = pallet_balances::Error::<Test>;
 
 use sp_core::H256;
 
-use sp_runtime::traits::IntegerSquareRoot;
+use sp_runtime::traits::{BadOrigin, BlakeTwo256, Hash, IntegerSquareRoot};
+
+/// `n` options with ids from 0 and no votes.
+fn options(n: u8) -> BoundedVec<Options<H256>, ConstU32<3>> {
+	BoundedVec::truncate_from((0..n).map(|id| Options { id, votes: 0, text: H256::random() }).collect())
+}
  
 
 
@@ -76,11 +87,35 @@ fn get_votes() {
 		assert!(new_votes.unwrap() == 200u128.integer_sqrt());
 
 		// Assert that the correct event was deposited
-		System::assert_last_event(Event::VotesEmited { who: 1, votes: 200u128.integer_sqrt()}.into());
+		System::assert_last_event(Event::VotesEmited { who: 1, votes: 200u128.integer_sqrt(), curve: Curve::SquareRoot }.into());
+
+		// an amount under the register fee gives votes too
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(2), 16));
+		assert_eq!(Voting::get_vote_amount(2), Some(4));
 
 	});
 }
 
+#[test]
+fn vote_power_curves() {
+	assert_eq!(Linear::votes(0), 0);
+	assert_eq!(Linear::votes(200), 200);
+
+	assert_eq!(SquareRoot::votes(0), 0);
+	assert_eq!(SquareRoot::votes(200), 14);
+
+	assert_eq!(Logarithmic::votes(0), 0);
+	assert_eq!(Logarithmic::votes(1), 1);
+	assert_eq!(Logarithmic::votes(200), 7);
+	assert_eq!(Logarithmic::votes(u128::MAX), 127);
+
+	assert_eq!(CappedLinear::<ConstU128<150>>::votes(100), 100);
+	assert_eq!(CappedLinear::<ConstU128<150>>::votes(200), 150);
+
+	assert_eq!(<CappedLinear<ConstU128<150>> as VotePowerCurve>::CURVE, Curve::CappedLinear);
+}
+
 #[test]
 fn create_first_proposal() {
 	new_test_ext().execute_with(|| {
@@ -89,15 +124,21 @@ fn create_first_proposal() {
 
 		// create vector of options 
 		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
-		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone()));
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+
+		assert!(Voting::get_active_proposal(1).is_some());
+		System::assert_last_event(Event::ProposalCreated { id: 1}.into());
 
-		assert!(Voting::get_active_proposal().is_some());
+		// a second proposal can be open at the same time
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		assert!(Voting::get_active_proposal(2).is_some());
+		assert_eq!(Voting::get_active_proposal_ids(), vec![1, 2]);
 
-		// if i want to create a second proposal i need to wait for the first one to expire
-		assert_noop!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options), Error::<Test>::ProposalAlreadyActive);
+		// cannot open more proposals than MaxActiveProposals
+		assert_noop!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options, BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None), Error::<Test>::TooManyActiveProposals);
 
 		// Assert that the correct event was deposited
-		System::assert_last_event(Event::ProposalCreated { id: 1}.into());
+		System::assert_last_event(Event::ProposalCreated { id: 2}.into());
 	});
 }
 
@@ -106,16 +147,16 @@ fn vote_proposal() {
 	new_test_ext().execute_with(|| {
 		// create vector of options
 		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
-		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone()));
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
 		// add a voter and get votes
 		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
 		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
 		
 		// vote successfully
 		let vote_vec_valid: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:0, votes:5}, VoteStruct {id:1, votes:2}, VoteStruct{id:2, votes:3}];
-		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), vote_vec_valid.clone()));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, vote_vec_valid.clone(), Conviction::None));
 
-		let active_proposal = Voting::get_active_proposal().unwrap();
+		let active_proposal = Voting::get_active_proposal(1).unwrap();
 
 		// verify that the votes are correct
 		assert!(active_proposal.options[0].votes == 5 && active_proposal.options[1].votes == 2 && active_proposal.options[2].votes == 3);
@@ -126,12 +167,12 @@ fn vote_proposal() {
 
 		// vote successfully voter 2
 		let vote_vec_valid_2: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:0, votes:5}, VoteStruct {id:1, votes:5}, VoteStruct{id:2, votes:0}];
-		assert_ok!(Voting::vote(RuntimeOrigin::signed(2), vote_vec_valid_2.clone()));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(2), 1, vote_vec_valid_2.clone(), Conviction::None));
 
 		// user cannot vote twice even if he not used all of his votes
-		assert_noop!(Voting::vote(RuntimeOrigin::signed(2), vote_vec_valid_2), Error::<Test>::AlreadyVoted);
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(2), 1, vote_vec_valid_2, Conviction::None), Error::<Test>::AlreadyVoted);
 
-		let active_proposal_updated = Voting::get_active_proposal().unwrap();
+		let active_proposal_updated = Voting::get_active_proposal(1).unwrap();
 
 		assert!(active_proposal_updated.options[0].votes == 10 && active_proposal_updated.options[1].votes == 7 && active_proposal_updated.options[2].votes == 3);
 	});	
@@ -142,7 +183,7 @@ fn cannot_vote_after_end_block() {
 	new_test_ext().execute_with(|| {
 		// create vector of options
 		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
-		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone()));
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
 		// add a voter and get votes
 		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
 		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
@@ -150,7 +191,7 @@ fn cannot_vote_after_end_block() {
 		// advance time and check that the user cannot vote
 		System::set_block_number(100);
 		let vote_vec_valid: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:0, votes:5}, VoteStruct {id:1, votes:2}, VoteStruct{id:2, votes:3}];
-		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), vote_vec_valid), Error::<Test>::ProposalFinished);
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), 1, vote_vec_valid, Conviction::None), Error::<Test>::ProposalFinished);
 	});
 }
 
@@ -159,14 +200,14 @@ fn not_enough_votes() {
 	new_test_ext().execute_with(|| {
 		// create vector of options
 		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
-		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone()));
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
 		// add a voter and get votes
 		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
 		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
 
 		// invalid votes (invalid amount of votes)
 		let vote_vec_invalid_votes: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:0, votes:5}, VoteStruct {id:1, votes:5}, VoteStruct{id:2, votes:3}];
-		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), vote_vec_invalid_votes.clone()), Error::<Test>::NotEnoughVotes);
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), 1, vote_vec_invalid_votes.clone(), Conviction::None), Error::<Test>::NotEnoughVotes);
 	});
 }
 
@@ -175,16 +216,16 @@ fn cannot_vote_twice() {
 	new_test_ext().execute_with(|| {
 		// create vector of options
 		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
-		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone()));
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
 		// add a voter and get votes
 		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
 		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
 
 		// vote successfully
 		let vote_vec_valid: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:0, votes:5}, VoteStruct {id:1, votes:2}, VoteStruct{id:2, votes:3}];
-		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), vote_vec_valid.clone()));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, vote_vec_valid.clone(), Conviction::None));
 		// user cannot vote twice
-		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), vote_vec_valid.clone()), Error::<Test>::AlreadyVoted);
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), 1, vote_vec_valid.clone(), Conviction::None), Error::<Test>::AlreadyVoted);
 	});
 }
 
@@ -193,11 +234,11 @@ fn not_register_vote_cannot_vote() {
 	new_test_ext().execute_with(|| {
 		// create vector of options
 		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
-		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone()));
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
 
 		//vector of the options that the user wants to vote
 		let vote_vec: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:0, votes:5}, VoteStruct {id:1, votes:5}, VoteStruct{id:3, votes:3}];
-		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), vote_vec.clone()), Error::<Test>::NotAVoter);
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), 1, vote_vec.clone(), Conviction::None), Error::<Test>::NotAVoter);
 	});
 }
 
@@ -206,7 +247,7 @@ fn vote_invalid_option_id() {
 	new_test_ext().execute_with(|| {
 		// create vector of options
 		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
-		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone()));
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
 
 		// add a voter and get votes
 		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
@@ -215,7 +256,7 @@ fn vote_invalid_option_id() {
 		// invalid votes (invalid vote id)
 		//vector of the options that the user wants to vote
 		let vote_vec_invalid_id: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:3, votes:1}, VoteStruct {id:1, votes:2}, VoteStruct{id:2, votes:3}];
-		assert_noop!(Voting::vote(RuntimeOrigin::signed(1),vote_vec_invalid_id.clone()), Error::<Test>::InvalidOptionId);
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), 1, vote_vec_invalid_id.clone(), Conviction::None), Error::<Test>::InvalidOptionId);
 	});
 }
 
@@ -226,30 +267,31 @@ fn close_proposal() {
 		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
 
 		System::set_block_number( 1);
+		// add voter
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(2), 144));
+
+		System::set_block_number( 2);
 		// try to close a proposal without one active
-		assert_noop!(Voting::end_proposal(RuntimeOrigin::signed(1)), Error::<Test>::NoActiveProposal);
+		assert_noop!(Voting::end_proposal(RuntimeOrigin::signed(1), 1), Error::<Test>::NoActiveProposal);
 
-		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone()));
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
 
 		// try to close a proposal before the end of the voting period
-		assert_noop!(Voting::end_proposal(RuntimeOrigin::signed(1)), Error::<Test>::ProposalNotFinished);
-
-		// add voter
-		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
-		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(2), 144));
+		assert_noop!(Voting::end_proposal(RuntimeOrigin::signed(1), 1), Error::<Test>::ProposalNotFinished);
 
 		// vote successfully voter 2
 		let vote_vec_valid_2: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:0, votes:6}, VoteStruct {id:1, votes:5}, VoteStruct{id:2, votes:0}];
-		assert_ok!(Voting::vote(RuntimeOrigin::signed(2), vote_vec_valid_2.clone()));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(2), 1, vote_vec_valid_2.clone(), Conviction::None));
 
 		
 
 		// End a proposal successfully
 		System::set_block_number( 15);
 
-		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
 		// clean the active proposal
-		assert!(Voting::get_active_proposal().is_none());
+		assert!(Voting::get_active_proposal(1).is_none());
 
 		// check the list of closed proposals
 		assert!(Voting::get_closed_proposal(1).is_some());
@@ -270,7 +312,7 @@ fn withdraw_votes() {
 		assert_noop!(Voting::withdraw(RuntimeOrigin::signed(1)), Error::<Test>::NotAVoter);
 		
 		// create proposal
-		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone()));
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
 		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
 		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
 
@@ -280,16 +322,2009 @@ fn withdraw_votes() {
 		// users vote
 		// vote successfully
 		let vote_vec_valid: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:0, votes:5}, VoteStruct {id:1, votes:2}, VoteStruct{id:2, votes:3}];
-		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), vote_vec_valid.clone()));
-		assert_ok!(Voting::vote(RuntimeOrigin::signed(2), vote_vec_valid.clone()));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, vote_vec_valid.clone(), Conviction::None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(2), 1, vote_vec_valid.clone(), Conviction::None));
 
 		// cannot withdraw votes before the end of the voting period
-		assert_noop!(Voting::withdraw(RuntimeOrigin::signed(1)), Error::<Test>::ProposalAlreadyActive);
+		assert_noop!(Voting::withdraw(RuntimeOrigin::signed(1)), Error::<Test>::VotesLocked);
 		// Set the end of the voting period
 		System::set_block_number( 15);
 		// end proposal
-		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
 		// user one withdraw is votes
 		assert_ok!(Voting::withdraw(RuntimeOrigin::signed(1)));
 	});
-}
\ No newline at end of file
+}
+
+#[test]
+fn vote_concurrent_proposals() {
+	new_test_ext().execute_with(|| {
+		// create two proposals
+		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		// add a voter and get votes
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+
+		// the voter can vote in both proposals
+		let vote_vec_1: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:0, votes:10}];
+		let vote_vec_2: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:2, votes:4}];
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, vote_vec_1.clone(), Conviction::None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 2, vote_vec_2, Conviction::None));
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), 1, vote_vec_1.clone(), Conviction::None), Error::<Test>::AlreadyVoted);
+
+		// cannot vote a proposal that is not active
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), 3, vote_vec_1, Conviction::None), Error::<Test>::NoActiveProposal);
+
+		assert!(Voting::get_active_proposal(1).unwrap().options[0].votes == 10);
+		assert!(Voting::get_active_proposal(2).unwrap().options[2].votes == 4);
+
+		// ending one proposal keeps the other open
+		System::set_block_number(15);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
+		assert_eq!(Voting::get_active_proposal_ids(), vec![2]);
+
+		// the votes are still locked in the second proposal
+		assert_noop!(Voting::withdraw(RuntimeOrigin::signed(1)), Error::<Test>::VotesLocked);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 2));
+		assert_ok!(Voting::withdraw(RuntimeOrigin::signed(1)));
+	});
+}
+
+#[test]
+fn expired_proposals_are_finalized_in_hooks() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+
+		// create two proposals
+		System::set_block_number(2);
+		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		let vote_vec: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:1, votes:10}];
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, vote_vec, Conviction::None));
+
+		// the proposals are still open in their end block
+		System::set_block_number(12);
+		Voting::on_initialize(12);
+		assert_eq!(Voting::get_active_proposal_ids(), vec![1, 2]);
+
+		// only one proposal is finalized in on_initialize
+		System::set_block_number(13);
+		Voting::on_initialize(13);
+		assert_eq!(Voting::get_active_proposal_ids(), vec![2]);
+		System::assert_last_event(Event::ProposalFinished { id: 1, winner_index: 1, winner_votes: 10 }.into());
+		assert!(Voting::get_closed_proposal(1).unwrap().winner_index == 1);
+
+		// the remaining one is finalized with the leftover weight
+		Voting::on_idle(13, Weight::zero());
+		assert_eq!(Voting::get_active_proposal_ids(), vec![2]);
+		Voting::on_idle(13, Weight::MAX);
+		assert!(Voting::get_active_proposal_ids().is_empty());
+		assert!(Voting::get_closed_proposal(2).is_some());
+
+		// the manual fallback has nothing left to end
+		assert_noop!(Voting::end_proposal(RuntimeOrigin::signed(1), 2), Error::<Test>::NoActiveProposal);
+	});
+}
+
+#[test]
+fn winner_option_call_is_enacted() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
+		let remark = <Preimage as StorePreimage>::bound(RuntimeCall::System(frame_system::Call::remark { remark: vec![1] })).unwrap();
+
+		// calls must be attached to existing options, one per option
+		assert_noop!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![(3, remark.clone())], None, None, None), Error::<Test>::InvalidOptionId);
+		assert_noop!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![(1, remark.clone()), (1, remark.clone())], None, None, None), Error::<Test>::InvalidOptionId);
+
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![(1, remark.clone())], None, None, None));
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![(2, remark.clone())], None, None, None));
+		let vote_vec: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:1, votes:10}];
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, vote_vec.clone(), Conviction::None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 2, vote_vec, Conviction::None));
+
+		// only the call of the winner option is scheduled
+		System::set_block_number(13);
+		Voting::on_initialize(13);
+		Voting::on_idle(13, Weight::MAX);
+		System::assert_has_event(Event::ProposalScheduled { id: 1, when: 15 }.into());
+		assert_eq!(Enactments::<Test>::get(15).len(), 1);
+		assert!(OptionCalls::<Test>::get(2).is_empty());
+
+		// the call is dispatched with the voting origin after the enactment delay
+		System::set_block_number(14);
+		Voting::on_initialize(14);
+		assert_eq!(Enactments::<Test>::get(15).len(), 1);
+		System::set_block_number(15);
+		Voting::on_initialize(15);
+		System::assert_has_event(Event::ProposalEnacted { id: 1, result: Err(DispatchError::BadOrigin) }.into());
+		assert!(Enactments::<Test>::get(15).is_empty());
+	});
+}
+
+#[test]
+fn query_finished_proposals_and_voters() {
+	new_test_ext().execute_with(|| {
+		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		let vote_vec: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:1, votes:10}];
+
+		// finish the proposals 1 and 2 and keep the 3 open
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, vote_vec, Conviction::None));
+		assert!(Voting::has_voted(1, 1));
+		assert!(!Voting::has_voted(1, 2));
+		assert!(!Voting::has_voted(2, 1));
+		System::set_block_number(15);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 2));
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options, BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+
+		let ids = |proposals: Vec<crate::FinishedProposal<Test>>| proposals.iter().map(|p| p.id).collect::<Vec<_>>();
+		assert_eq!(ids(Voting::get_closed_proposals(0, 10)), vec![1, 2]);
+		assert_eq!(ids(Voting::get_closed_proposals(1, 1)), vec![1]);
+		assert_eq!(ids(Voting::get_closed_proposals(2, 10)), vec![2]);
+		assert!(Voting::get_closed_proposals(3, 10).is_empty());
+	});
+}
+
+#[test]
+fn proposal_thresholds() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(2), 64));
+		let vote_vec_1: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:0, votes:10}];
+		let vote_vec_2: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:1, votes:8}];
+
+		// not enough votes and not enough voters
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds { min_votes: 20, min_voters: 0, approval: None }, None, bounded_vec![], None, None, None));
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds { min_votes: 0, min_voters: 2, approval: None }, None, bounded_vec![], None, None, None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, vote_vec_1.clone(), Conviction::None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 2, vote_vec_1.clone(), Conviction::None));
+		System::set_block_number(15);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
+		System::assert_last_event(Event::ProposalRejected { id: 1, reason: RejectionReason::NotEnoughVotes }.into());
+		assert_eq!(Voting::get_closed_proposal(1).unwrap().status, ProposalStatus::NoQuorum);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 2));
+		System::assert_last_event(Event::ProposalRejected { id: 2, reason: RejectionReason::NotEnoughVoters }.into());
+		assert_eq!(Voting::get_closed_proposal(2).unwrap().status, ProposalStatus::NoQuorum);
+
+		// the winner option needs 60% of the votes and gets 10 of 18
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds { min_votes: 0, min_voters: 2, approval: Some(Perbill::from_percent(60)) }, None, bounded_vec![], None, None, None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 3, vote_vec_1.clone(), Conviction::None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(2), 3, vote_vec_2.clone(), Conviction::None));
+		// a proposal without votes never reaches the quorum
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		System::set_block_number(30);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 3));
+		System::assert_last_event(Event::ProposalRejected { id: 3, reason: RejectionReason::NotEnoughApproval }.into());
+		assert_eq!(Voting::get_closed_proposal(3).unwrap().status, ProposalStatus::Rejected);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 4));
+		System::assert_last_event(Event::ProposalRejected { id: 4, reason: RejectionReason::NotEnoughVotes }.into());
+
+		// the same votes pass with a lower approval threshold
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options, BallotType::Split, false, Thresholds { min_votes: 18, min_voters: 2, approval: Some(Perbill::from_percent(55)) }, None, bounded_vec![], None, None, None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 5, vote_vec_1, Conviction::None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(2), 5, vote_vec_2, Conviction::None));
+		System::set_block_number(45);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 5));
+		System::assert_last_event(Event::ProposalFinished { id: 5, winner_index: 0, winner_votes: 10 }.into());
+		assert_eq!(Voting::get_closed_proposal(5).unwrap().status, ProposalStatus::Finished);
+	});
+}
+
+#[test]
+fn tie_break_policies() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
+		let remark = <Preimage as StorePreimage>::bound(RuntimeCall::System(frame_system::Call::remark { remark: vec![1] })).unwrap();
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		let tie_vec: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:1, votes:4}, VoteStruct {id:2, votes:4}];
+		let tied: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:1, votes:4}, VoteStruct {id:2, votes:4}];
+
+		// the default policy declares the tie
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		// the origin picks the winner
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), Some(TieBreak::Origin), bounded_vec![(2, remark.clone())], None, None, None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, tie_vec.clone(), Conviction::None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 2, tie_vec.clone(), Conviction::None));
+		System::set_block_number(15);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
+		System::assert_last_event(Event::ProposalTied { id: 1, options: bounded_vec![1, 2], runoff: None }.into());
+		let finished_proposal = Voting::get_closed_proposal(1).unwrap();
+		assert_eq!(finished_proposal.status, ProposalStatus::Tied);
+		assert_eq!(finished_proposal.tied, tied);
+		assert_noop!(Voting::resolve_tie(RuntimeOrigin::root(), 1, 1), Error::<Test>::NotTied);
+
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 2));
+		assert_eq!(Voting::get_closed_proposal(2).unwrap().status, ProposalStatus::Tied);
+		assert_noop!(Voting::resolve_tie(RuntimeOrigin::signed(1), 2, 2), BadOrigin);
+		assert_noop!(Voting::resolve_tie(RuntimeOrigin::root(), 2, 0), Error::<Test>::InvalidOptionId);
+		assert_ok!(Voting::resolve_tie(RuntimeOrigin::root(), 2, 2));
+		System::assert_has_event(Event::ProposalFinished { id: 2, winner_index: 2, winner_votes: 4 }.into());
+		System::assert_last_event(Event::ProposalScheduled { id: 2, when: 17 }.into());
+		assert_eq!(Voting::get_closed_proposal(2).unwrap().status, ProposalStatus::Finished);
+		assert_noop!(Voting::resolve_tie(RuntimeOrigin::root(), 2, 1), Error::<Test>::NotTied);
+
+		// a runoff proposal is opened among the tied options with their calls
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), Some(TieBreak::Runoff), bounded_vec![(0, remark.clone()), (2, remark.clone())], None, None, None));
+		// randomness picks one of the tied options
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options, BallotType::Split, false, Thresholds::default(), Some(TieBreak::Random), bounded_vec![], None, None, None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 3, tie_vec.clone(), Conviction::None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 4, tie_vec, Conviction::None));
+		System::set_block_number(30);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 3));
+		System::assert_last_event(Event::ProposalTied { id: 3, options: bounded_vec![1, 2], runoff: Some(5) }.into());
+		let runoff = Voting::get_active_proposal(5).unwrap();
+		assert_eq!(runoff.options.iter().map(|o| (o.id, o.votes)).collect::<Vec<_>>(), vec![(1, 0), (2, 0)]);
+		assert_eq!(runoff.tie_break, TieBreak::Declare);
+		assert_eq!(OptionCalls::<Test>::get(5).iter().map(|(id, _)| *id).collect::<Vec<_>>(), vec![2]);
+
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 4));
+		let finished_proposal = Voting::get_closed_proposal(4).unwrap();
+		assert_eq!(finished_proposal.status, ProposalStatus::Finished);
+		assert_eq!(finished_proposal.tied, tied);
+		assert!(finished_proposal.winner_index == 1 || finished_proposal.winner_index == 2);
+	});
+}
+
+#[test]
+fn change_and_retract_ballot() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(2), 64));
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options, BallotType::RankedChoice, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+
+		// a ballot can only be changed after voting
+		let vote_vec: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:0, votes:3}];
+		assert_noop!(Voting::revote(RuntimeOrigin::signed(1), 1, vote_vec.clone(), Conviction::None), Error::<Test>::NotVoted);
+		assert_noop!(Voting::unvote(RuntimeOrigin::signed(1), 1), Error::<Test>::NotVoted);
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, vote_vec.clone(), Conviction::None));
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), 1, vote_vec, Conviction::None), Error::<Test>::AlreadyVoted);
+
+		// the leftover votes can be spent in a new ballot
+		let new_vote_vec: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:0, votes:5}, VoteStruct {id:1, votes:5}];
+		let too_many_votes: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:0, votes:11}];
+		assert_noop!(Voting::revote(RuntimeOrigin::signed(1), 1, too_many_votes, Conviction::None), Error::<Test>::NotEnoughVotes);
+		assert_ok!(Voting::revote(RuntimeOrigin::signed(1), 1, new_vote_vec, Conviction::None));
+		let active_proposal = Voting::get_active_proposal(1).unwrap();
+		assert!(active_proposal.options[0].votes == 5 && active_proposal.options[1].votes == 5 && active_proposal.voters == 1);
+
+		// a retracted ballot leaves the tallies and unlocks the votes
+		assert_ok!(Voting::unvote(RuntimeOrigin::signed(1), 1));
+		System::assert_last_event(Event::VoteRetracted { who: 1, proposal_id: 1 }.into());
+		let active_proposal = Voting::get_active_proposal(1).unwrap();
+		assert!(active_proposal.options.iter().all(|o| o.votes == 0) && active_proposal.voters == 0);
+		assert!(!Voting::has_voted(1, 1));
+		assert_noop!(Voting::unvote(RuntimeOrigin::signed(1), 1), Error::<Test>::NotVoted);
+		let final_vote_vec: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:2, votes:10}];
+		assert_ok!(Voting::revote(RuntimeOrigin::signed(1), 1, final_vote_vec, Conviction::None));
+		assert!(Voting::get_active_proposal(1).unwrap().options[2].votes == 10);
+
+		// rankings can be changed and retracted too
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(2), 2, bounded_vec![0], Conviction::None));
+		assert_noop!(Voting::revote(RuntimeOrigin::signed(2), 2, bounded_vec![VoteStruct {id:0, votes:1}], Conviction::None), Error::<Test>::InvalidBallotType);
+		assert_ok!(Voting::revote_ranked(RuntimeOrigin::signed(2), 2, bounded_vec![1, 2], Conviction::None));
+		let active_proposal = Voting::get_active_proposal(2).unwrap();
+		assert!(active_proposal.options[0].votes == 0 && active_proposal.options[1].votes == 8);
+		assert_eq!(crate::RankedBallots::<Test>::get(2).iter().map(|b| (b.ranking.to_vec(), b.votes)).collect::<Vec<_>>(), vec![(vec![1, 2], 8)]);
+		assert_ok!(Voting::unvote(RuntimeOrigin::signed(2), 2));
+		assert!(crate::RankedBallots::<Test>::get(2).is_empty());
+		assert_ok!(Voting::withdraw(RuntimeOrigin::signed(2)));
+
+		// the ballots cannot change after the end block
+		System::set_block_number(13);
+		assert_noop!(Voting::unvote(RuntimeOrigin::signed(1), 1), Error::<Test>::ProposalFinished);
+	});
+}
+
+#[test]
+fn conviction_votes_lock_stake() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(2), 64));
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options, BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+
+		// the conviction multiplies the votes of the voter
+		let too_many_votes: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:0, votes:31}];
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), 1, too_many_votes, Conviction::Locked3x), Error::<Test>::NotEnoughVotes);
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct {id:0, votes:30}], Conviction::Locked3x));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(2), 1, bounded_vec![VoteStruct {id:1, votes:8}], Conviction::None));
+		assert!(Voting::get_active_proposal(1).unwrap().options[0].votes == 30);
+
+		// the stake is locked for 2 periods after the end block, no lock without conviction
+		assert_eq!(VoteLocks::<Test>::get(1), Some((100, 22)));
+		assert_eq!(VoteLocks::<Test>::get(2), None);
+		assert!(Balances::locks(1).is_empty());
+		assert!(Balances::locks(2).is_empty());
+		assert_noop!(Voting::unlock(RuntimeOrigin::signed(2)), Error::<Test>::NoVoteLock);
+		assert_noop!(Voting::unlock(RuntimeOrigin::signed(1)), Error::<Test>::VoteLockNotExpired);
+
+		// the withdrawn stake stays locked
+		System::set_block_number(13);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
+		assert_ok!(Voting::withdraw(RuntimeOrigin::signed(1)));
+		assert_noop!(Balances::transfer(RuntimeOrigin::signed(1), 2, 950), BalanceError::LiquidityRestrictions);
+
+		// once the lock expires it can be removed
+		System::set_block_number(23);
+		assert_ok!(Voting::unlock(RuntimeOrigin::signed(1)));
+		System::assert_last_event(Event::Unlocked { who: 1 }.into());
+		assert_eq!(VoteLocks::<Test>::get(1), None);
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(1), 2, 950));
+	});
+}
+
+#[test]
+fn vote_power_snapshot() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		assert_eq!(Voting::get_active_proposal(1).unwrap().snapshot_block, 1);
+
+		// the reserves added after the snapshot do not count in the running proposal, even in the
+		// block it was created
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 300));
+		assert_eq!(Voting::get_vote_amount(1), Some(20));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(2), 64));
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct {id:0, votes:11}], Conviction::None), Error::<Test>::NotEnoughVotes);
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(2), 1, bounded_vec![VoteStruct {id:0, votes:1}], Conviction::None), Error::<Test>::NotEnoughVotes);
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct {id:0, votes:10}], Conviction::None));
+
+		// they count in the later proposals
+		System::set_block_number(3);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options, BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 2, bounded_vec![VoteStruct {id:0, votes:20}], Conviction::None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(2), 2, bounded_vec![VoteStruct {id:1, votes:8}], Conviction::None));
+		assert_eq!(crate::PowerCheckpoints::<Test>::get(1).into_inner(), vec![(1, 10), (2, 20)]);
+	});
+}
+
+#[test]
+fn submit_and_launch_queued_proposals() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+
+		// only the voters can submit proposals, root keeps launching them directly
+		assert_noop!(Voting::submit_proposal(RuntimeOrigin::signed(3), H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![]), Error::<Test>::NotAVoter);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+
+		// the submitted proposals wait in a bounded queue with a deposit reserved
+		assert_ok!(Voting::submit_proposal(RuntimeOrigin::signed(1), H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![]));
+		System::assert_last_event(Event::ProposalSubmitted { who: 1, index: 0 }.into());
+		assert_ok!(Voting::submit_proposal(RuntimeOrigin::signed(2), H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![]));
+		assert_noop!(Voting::submit_proposal(RuntimeOrigin::signed(1), H256::random(), options, BallotType::Split, false, Thresholds::default(), None, bounded_vec![]), Error::<Test>::TooManyQueuedProposals);
+		assert_eq!(Balances::reserved_balance(1), 120);
+
+		// the deposit gives no votes and cannot be withdrawn
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_eq!(Voting::get_vote_amount(1), Some(200u128.integer_sqrt()));
+		assert_noop!(Voting::withdraw(RuntimeOrigin::signed(1)), Error::<Test>::VotesLocked);
+
+		// a spam proposal is removed and its deposit slashed
+		assert_noop!(Voting::cancel_queued_proposal(RuntimeOrigin::signed(1), 1), BadOrigin);
+		assert_ok!(Voting::cancel_queued_proposal(RuntimeOrigin::root(), 1));
+		System::assert_last_event(Event::ProposalSlashed { index: 1, who: 2, amount: 20 }.into());
+		assert_eq!((Balances::free_balance(2), Balances::reserved_balance(2)), (450, 0));
+		assert_noop!(Voting::cancel_queued_proposal(RuntimeOrigin::root(), 1), Error::<Test>::ProposalNotQueued);
+
+		// the queued proposal is launched when an active proposal ends and the deposit is refunded
+		System::set_block_number(12);
+		Voting::on_initialize(12);
+		System::assert_last_event(Event::ProposalLaunched { index: 0, id: 3 }.into());
+		assert!(Voting::get_active_proposal(3).is_some());
+		assert!(crate::ProposalQueue::<Test>::get().is_empty());
+		assert_eq!(Balances::reserved_balance(1), 200);
+	});
+}
+
+#[test]
+fn second_and_launch_queued_proposals() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(3)));
+		assert_ok!(Voting::submit_proposal(RuntimeOrigin::signed(1), H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![]));
+		assert_ok!(Voting::submit_proposal(RuntimeOrigin::signed(2), H256::random(), options, BallotType::Split, false, Thresholds::default(), None, bounded_vec![]));
+
+		// the voters back a queued proposal with a deposit
+		assert_noop!(Voting::second(RuntimeOrigin::signed(4), 1), Error::<Test>::NotAVoter);
+		assert_noop!(Voting::second(RuntimeOrigin::signed(3), 9), Error::<Test>::ProposalNotQueued);
+		assert_ok!(Voting::second(RuntimeOrigin::signed(3), 1));
+		System::assert_last_event(Event::Seconded { who: 3, index: 1 }.into());
+		assert_ok!(Voting::second(RuntimeOrigin::signed(1), 1));
+		assert_noop!(Voting::second(RuntimeOrigin::signed(2), 1), Error::<Test>::TooManySeconds);
+		assert_eq!(Balances::reserved_balance(3), 20);
+		assert_eq!(Balances::reserved_balance(1), 40);
+
+		// the proposals are only launched at the launch period
+		System::set_block_number(3);
+		Voting::on_initialize(3);
+		assert_eq!(crate::ProposalQueue::<Test>::get().len(), 2);
+
+		// the most seconded proposal goes first and all its deposits are refunded
+		System::set_block_number(4);
+		Voting::on_initialize(4);
+		System::assert_last_event(Event::ProposalLaunched { index: 1, id: 1 }.into());
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::reserved_balance(3), 0);
+		assert_eq!(Balances::reserved_balance(1), 20);
+
+		System::set_block_number(8);
+		Voting::on_initialize(8);
+		System::assert_last_event(Event::ProposalLaunched { index: 0, id: 2 }.into());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert!(crate::ProposalQueue::<Test>::get().is_empty());
+	});
+}
+
+#[test]
+fn cancel_and_veto_proposals() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
+		let text = H256::random();
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), text, options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options.clone(), BallotType::Split, true, Thresholds::default(), None, bounded_vec![], None, None, None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct {id:0, votes:10}], Conviction::None));
+		assert_ok!(Voting::commit_vote(RuntimeOrigin::signed(1), 2, H256::random()));
+
+		// the cancelled proposal is stored without tallies
+		assert_noop!(Voting::cancel_proposal(RuntimeOrigin::signed(1), 1), BadOrigin);
+		assert_ok!(Voting::cancel_proposal(RuntimeOrigin::root(), 1));
+		System::assert_last_event(Event::ProposalCancelled { id: 1 }.into());
+		let cancelled = Voting::get_closed_proposal(1).unwrap();
+		assert!(cancelled.status == ProposalStatus::Cancelled && cancelled.options_votes.iter().all(|o| o.votes == 0));
+		assert!(Voting::get_active_proposal(1).is_none());
+		assert_noop!(Voting::cancel_proposal(RuntimeOrigin::root(), 1), Error::<Test>::NoActiveProposal);
+
+		// the stakes are untouched and the committed ballots are never slashed
+		assert_ok!(Voting::cancel_proposal(RuntimeOrigin::root(), 2));
+		assert_noop!(Voting::slash_unrevealed(RuntimeOrigin::signed(2), 2, 1), Error::<Test>::ProposalCancelled);
+		assert_eq!(Balances::reserved_balance(1), 100);
+		assert_ok!(Voting::withdraw(RuntimeOrigin::signed(1)));
+
+		// a vetoed text cannot be proposed until the cooldown ends
+		assert_noop!(Voting::veto(RuntimeOrigin::signed(1), text), BadOrigin);
+		assert_ok!(Voting::veto(RuntimeOrigin::root(), text));
+		System::assert_last_event(Event::ProposalVetoed { text, until: 22 }.into());
+		assert_noop!(Voting::set_proposal(RuntimeOrigin::root(), text, options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None), Error::<Test>::ProposalVetoed);
+		System::set_block_number(23);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), text, options, BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+	});
+}
+
+#[test]
+fn scheduled_proposal_duration() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+
+		// the start block cannot be in the past nor more than `MaxStartDelay` blocks ahead and the
+		// duration is bounded
+		System::set_block_number(6);
+		assert_noop!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], Some(4), None, None), Error::<Test>::InvalidStartBlock);
+		assert_noop!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], Some(57), None, None), Error::<Test>::InvalidStartBlock);
+		assert_noop!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, Some(1), None), Error::<Test>::InvalidDuration);
+		assert_noop!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, Some(11), None), Error::<Test>::InvalidDuration);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options, BallotType::Split, false, Thresholds::default(), None, bounded_vec![], Some(50), Some(3), None));
+		let proposal = Voting::get_active_proposal(1).unwrap();
+		assert_eq!((proposal.start_block, proposal.end_block), (50, 53));
+
+		// the proposal only accepts votes from its start block
+		let vote_vec: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:0, votes:10}];
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), 1, vote_vec.clone(), Conviction::None), Error::<Test>::ProposalNotStarted);
+		System::set_block_number(50);
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, vote_vec, Conviction::None));
+
+		System::set_block_number(54);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
+		assert!(Voting::get_closed_proposal(1).unwrap().status == ProposalStatus::Finished);
+	});
+}
+
+#[test]
+fn approval_ballots() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// add voters with 10, 8 and 9 votes
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(2), 64));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(3)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(3), 81));
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::Approval, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+
+		// every approved option gets all the votes of the voter
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct {id:0, votes:2}], Conviction::None), Error::<Test>::InvalidScore);
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct {id:0, votes:1}, VoteStruct {id:0, votes:1}], Conviction::None), Error::<Test>::InvalidOptionId);
+		assert_noop!(Voting::vote_ranked(RuntimeOrigin::signed(1), 1, bounded_vec![0, 1], Conviction::None), Error::<Test>::InvalidBallotType);
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct {id:0, votes:1}, VoteStruct {id:1, votes:1}], Conviction::None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(2), 1, bounded_vec![VoteStruct {id:1, votes:1}], Conviction::None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(3), 1, bounded_vec![VoteStruct {id:2, votes:1}], Conviction::None));
+		let tallies: Vec<u128> = Voting::get_active_proposal(1).unwrap().options.iter().map(|o| o.votes).collect();
+		assert_eq!(tallies, vec![10, 18, 9]);
+
+		System::set_block_number(13);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
+		assert_eq!(Voting::get_closed_proposal(1).unwrap().winner_index, 1);
+	});
+}
+
+#[test]
+fn score_ballots() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// add voters with 10, 8 and 9 votes
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(2), 64));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(3)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(3), 81));
+		System::set_block_number(2);
+		assert_noop!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::Score(0), false, Thresholds::default(), None, bounded_vec![], None, None, None), Error::<Test>::InvalidBallotType);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::Score(5), false, Thresholds::default(), None, bounded_vec![], None, None, None));
+
+		// every option gets its score times the votes of the voter
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct {id:0, votes:6}], Conviction::None), Error::<Test>::InvalidScore);
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct {id:0, votes:5}, VoteStruct {id:1, votes:2}], Conviction::None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(2), 1, bounded_vec![VoteStruct {id:1, votes:5}], Conviction::None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(3), 1, bounded_vec![VoteStruct {id:0, votes:1}, VoteStruct {id:2, votes:3}], Conviction::None));
+		assert_ok!(Voting::revote(RuntimeOrigin::signed(3), 1, bounded_vec![VoteStruct {id:2, votes:5}], Conviction::None));
+		let tallies: Vec<u128> = Voting::get_active_proposal(1).unwrap().options.iter().map(|o| o.votes).collect();
+		assert_eq!(tallies, vec![50, 60, 45]);
+
+		System::set_block_number(13);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
+		assert_eq!(Voting::get_closed_proposal(1).unwrap().winner_index, 1);
+	});
+}
+
+#[test]
+fn ranked_choice_proposal() {
+	new_test_ext().execute_with(|| {
+		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options, BallotType::RankedChoice, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+
+		// add voters with 10, 8 and 9 votes
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(2), 64));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(3)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(3), 81));
+
+		// a split ballot is not valid in a ranked-choice proposal
+		let vote_vec: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:0, votes:5}];
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), 1, vote_vec, Conviction::None), Error::<Test>::InvalidBallotType);
+
+		// invalid rankings
+		assert_noop!(Voting::vote_ranked(RuntimeOrigin::signed(1), 1, bounded_vec![], Conviction::None), Error::<Test>::InvalidRanking);
+		assert_noop!(Voting::vote_ranked(RuntimeOrigin::signed(1), 1, bounded_vec![0, 0], Conviction::None), Error::<Test>::InvalidRanking);
+		assert_noop!(Voting::vote_ranked(RuntimeOrigin::signed(1), 1, bounded_vec![0, 5], Conviction::None), Error::<Test>::InvalidOptionId);
+
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(1), 1, bounded_vec![0], Conviction::None));
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(2), 1, bounded_vec![1, 2], Conviction::None));
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(3), 1, bounded_vec![2], Conviction::None));
+		assert_noop!(Voting::vote_ranked(RuntimeOrigin::signed(3), 1, bounded_vec![2], Conviction::None), Error::<Test>::AlreadyVoted);
+
+		// the options keep the first preferences
+		let active_proposal = Voting::get_active_proposal(1).unwrap();
+		assert!(active_proposal.options[0].votes == 10 && active_proposal.options[1].votes == 8 && active_proposal.options[2].votes == 9);
+
+		System::set_block_number(15);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
+
+		// option 1 is eliminated and its votes are transferred to option 2
+		let finished_proposal = Voting::get_closed_proposal(1).unwrap();
+		assert_eq!(finished_proposal.winner_index, 2);
+		assert_eq!(finished_proposal.rounds.to_vec(), vec![
+			RunoffRound::<Test> { tallies: bounded_vec![VoteStruct {id:0, votes:10}, VoteStruct {id:1, votes:8}, VoteStruct {id:2, votes:9}], elected: bounded_vec![], eliminated: Some(1) },
+			RunoffRound::<Test> { tallies: bounded_vec![VoteStruct {id:0, votes:10}, VoteStruct {id:2, votes:17}], elected: bounded_vec![], eliminated: None },
+		]);
+		System::assert_last_event(Event::ProposalFinished { id: 1, winner_index: 2, winner_votes: 17 }.into());
+	});
+}
+
+#[test]
+fn condorcet_ballots_fill_the_pairwise_matrix() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// add voters with 10, 8 and 9 votes
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(2), 64));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(3)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(3), 81));
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::Condorcet, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct {id:0, votes:5}], Conviction::None), Error::<Test>::InvalidBallotType);
+
+		// a ranked option is preferred over the options ranked after it and the unranked ones
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(1), 1, bounded_vec![0, 1, 2], Conviction::None));
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(2), 1, bounded_vec![1, 2, 0], Conviction::None));
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(3), 1, bounded_vec![2, 0], Conviction::None));
+		let pairwise = vec![0, 19, 10, 8, 0, 18, 17, 9, 0];
+		assert_eq!(PairwiseMatrices::<Test>::get(1).to_vec(), pairwise);
+
+		// the matrix is updated when a ballot is retracted and cast again
+		assert_ok!(Voting::unvote(RuntimeOrigin::signed(2), 1));
+		assert_eq!(PairwiseMatrices::<Test>::get(1).to_vec(), vec![0, 19, 10, 0, 0, 10, 9, 9, 0]);
+		assert_ok!(Voting::revote_ranked(RuntimeOrigin::signed(2), 1, bounded_vec![1, 2, 0], Conviction::None));
+		assert_eq!(PairwiseMatrices::<Test>::get(1).to_vec(), pairwise);
+	});
+}
+
+#[test]
+fn condorcet_cycle_is_broken_at_the_weakest_link() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// add voters with 10, 8 and 9 votes
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(2), 64));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(3)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(3), 81));
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::Condorcet, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+
+		// the preferences form a cycle: 0 beats 1, 1 beats 2 and 2 beats 0
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(1), 1, bounded_vec![0, 1, 2], Conviction::None));
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(2), 1, bounded_vec![1, 2, 0], Conviction::None));
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(3), 1, bounded_vec![2, 0], Conviction::None));
+
+		System::set_block_number(13);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
+
+		// the weakest link of the cycle, 2 over 0, is dropped and 0 wins
+		let finished_proposal = Voting::get_closed_proposal(1).unwrap();
+		assert_eq!(finished_proposal.status, ProposalStatus::Finished);
+		assert_eq!(finished_proposal.winner_index, 0);
+		assert_eq!(finished_proposal.ranking.to_vec(), vec![0, 1, 2]);
+		assert_eq!(finished_proposal.pairwise.to_vec(), vec![0, 19, 10, 8, 0, 18, 17, 9, 0]);
+		assert!(PairwiseMatrices::<Test>::get(1).is_empty());
+		System::assert_last_event(Event::ProposalFinished { id: 1, winner_index: 0, winner_votes: 10 }.into());
+	});
+}
+
+#[test]
+fn single_transferable_vote_proposal_needs_valid_seats() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let remark = <Preimage as StorePreimage>::bound(RuntimeCall::System(frame_system::Call::remark { remark: vec![1] })).unwrap();
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+
+		// the seats must be fewer than the options and the options cannot have calls
+		System::set_block_number(2);
+		assert_noop!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::SingleTransferable(0), false, Thresholds::default(), None, bounded_vec![], None, None, None), Error::<Test>::InvalidSeats);
+		assert_noop!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::SingleTransferable(3), false, Thresholds::default(), None, bounded_vec![], None, None, None), Error::<Test>::InvalidSeats);
+		assert_noop!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::SingleTransferable(2), false, Thresholds::default(), None, bounded_vec![(0, remark)], None, None, None), Error::<Test>::CallsNotAllowed);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::SingleTransferable(2), false, Thresholds::default(), None, bounded_vec![], None, None, None));
+
+		// the ballots are ranked
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct {id:0, votes:5}], Conviction::None), Error::<Test>::InvalidBallotType);
+	});
+}
+
+#[test]
+fn single_transferable_vote_transfers_the_surplus() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// add voters with 10, 8, 10 and 8 votes
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(2), 64));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(3)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(3), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(4)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(4), 64));
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::SingleTransferable(2), false, Thresholds::default(), None, bounded_vec![], None, None, None));
+
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(1), 1, bounded_vec![0], Conviction::None));
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(2), 1, bounded_vec![1, 0], Conviction::None));
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(3), 1, bounded_vec![2, 1], Conviction::None));
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(4), 1, bounded_vec![2, 0], Conviction::None));
+
+		System::set_block_number(13);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
+
+		// the quota is 36 / 3 + 1 = 13. Option 2 is elected and its ballots go on at 5/18 of their
+		// value, then option 1 is eliminated and option 0 takes the last seat
+		let finished_proposal = Voting::get_closed_proposal(1).unwrap();
+		assert_eq!(finished_proposal.winners.to_vec(), vec![2, 0]);
+		assert_eq!(finished_proposal.winner_index, 2);
+		assert_eq!(finished_proposal.rounds.to_vec(), vec![
+			RunoffRound::<Test> { tallies: bounded_vec![VoteStruct {id:0, votes:10}, VoteStruct {id:1, votes:8}, VoteStruct {id:2, votes:18}], elected: bounded_vec![2], eliminated: None },
+			RunoffRound::<Test> { tallies: bounded_vec![VoteStruct {id:0, votes:12}, VoteStruct {id:1, votes:10}], elected: bounded_vec![], eliminated: Some(1) },
+			RunoffRound::<Test> { tallies: bounded_vec![VoteStruct {id:0, votes:20}], elected: bounded_vec![0], eliminated: None },
+		]);
+		System::assert_last_event(Event::ProposalFinished { id: 1, winner_index: 2, winner_votes: 18 }.into());
+	});
+}
+
+#[test]
+fn commit_reveal_secret_proposal() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
+		// add voters with 10, 8 and 9 votes
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(2), 64));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(3)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(3), 81));
+
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options, BallotType::Split, true, Thresholds::default(), None, bounded_vec![], None, None, None));
+
+		let vote_vec_1: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:0, votes:10}];
+		let vote_vec_2: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:1, votes:8}];
+		let salt_1 = [1u8; 32];
+		let salt_2 = [2u8; 32];
+
+		// the ballots of a secret proposal cannot be public
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), 1, vote_vec_1.clone(), Conviction::None), Error::<Test>::SecretBallotRequired);
+
+		assert_ok!(Voting::commit_vote(RuntimeOrigin::signed(1), 1, BlakeTwo256::hash_of(&(vote_vec_1.clone(), salt_1))));
+		assert_ok!(Voting::commit_vote(RuntimeOrigin::signed(2), 1, BlakeTwo256::hash_of(&(vote_vec_2.clone(), salt_2))));
+		assert_ok!(Voting::commit_vote(RuntimeOrigin::signed(3), 1, H256::random()));
+		System::assert_last_event(Event::VoteCommitted { who: 3, proposal_id: 1 }.into());
+		assert_noop!(Voting::commit_vote(RuntimeOrigin::signed(1), 1, H256::random()), Error::<Test>::AlreadyVoted);
+
+		// the committed votes are not tallied
+		assert!(Voting::get_active_proposal(1).unwrap().options.iter().all(|o| o.votes == 0));
+
+		// the ballots cannot be revealed during the commit phase
+		assert_noop!(Voting::reveal_vote(RuntimeOrigin::signed(1), 1, vote_vec_1.clone(), salt_1), Error::<Test>::NotInRevealPhase);
+
+		System::set_block_number(13);
+		assert_noop!(Voting::commit_vote(RuntimeOrigin::signed(4), 1, H256::random()), Error::<Test>::NotAVoter);
+		assert_noop!(Voting::reveal_vote(RuntimeOrigin::signed(1), 1, vote_vec_1.clone(), salt_2), Error::<Test>::InvalidReveal);
+		assert_noop!(Voting::reveal_vote(RuntimeOrigin::signed(4), 1, vote_vec_1.clone(), salt_1), Error::<Test>::NoCommitment);
+		assert_ok!(Voting::reveal_vote(RuntimeOrigin::signed(1), 1, vote_vec_1.clone(), salt_1));
+		assert_ok!(Voting::reveal_vote(RuntimeOrigin::signed(2), 1, vote_vec_2.clone(), salt_2));
+		assert_noop!(Voting::reveal_vote(RuntimeOrigin::signed(1), 1, vote_vec_1, salt_1), Error::<Test>::NoCommitment);
+
+		let active_proposal = Voting::get_active_proposal(1).unwrap();
+		assert!(active_proposal.options[0].votes == 10 && active_proposal.options[1].votes == 8);
+
+		// the proposal closes after the reveal phase
+		System::set_block_number(17);
+		Voting::on_initialize(17);
+		assert!(Voting::get_active_proposal(1).is_some());
+		System::set_block_number(18);
+		Voting::on_initialize(18);
+		assert!(Voting::get_closed_proposal(1).unwrap().winner_index == 0);
+
+		// the voter that did not reveal cannot withdraw until slashed
+		assert_noop!(Voting::withdraw(RuntimeOrigin::signed(3)), Error::<Test>::VotesLocked);
+		assert_noop!(Voting::slash_unrevealed(RuntimeOrigin::signed(1), 1, 1), Error::<Test>::NoCommitment);
+		let pot = Balances::free_balance(Voting::reward_pot());
+		assert_ok!(Voting::slash_unrevealed(RuntimeOrigin::signed(1), 1, 3));
+		System::assert_last_event(Event::UnrevealedVoteSlashed { who: 3, proposal_id: 1, amount: 8 }.into());
+		// the slashed stake is not burnt but paid to the reward pot
+		assert_eq!(Balances::free_balance(Voting::reward_pot()), pot + 8);
+		assert_eq!(Balances::reserved_balance(3), 73);
+		assert_eq!(Voting::get_vote_amount(3), Some(73u128.integer_sqrt()));
+		assert_ok!(Voting::withdraw(RuntimeOrigin::signed(3)));
+	});
+}
+
+#[test]
+fn delegate_votes() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// add voters with 10, 8, 9 and 7 votes
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(2), 64));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(3)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(3), 81));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(4)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(4), 49));
+
+		assert_noop!(Voting::delegate(RuntimeOrigin::signed(5), 1), Error::<Test>::NotAVoter);
+		assert_noop!(Voting::delegate(RuntimeOrigin::signed(1), 5), Error::<Test>::NotAVoter);
+		assert_noop!(Voting::delegate(RuntimeOrigin::signed(1), 1), Error::<Test>::SelfDelegation);
+
+		// 3 -> 2 -> 1
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(2), 1));
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(3), 2));
+		System::assert_last_event(Event::Delegated { who: 3, to: 2 }.into());
+		assert_noop!(Voting::delegate(RuntimeOrigin::signed(3), 1), Error::<Test>::AlreadyDelegating);
+		assert_noop!(Voting::delegate(RuntimeOrigin::signed(1), 3), Error::<Test>::DelegationCycle);
+		assert_noop!(Voting::delegate(RuntimeOrigin::signed(4), 3), Error::<Test>::DelegationTooDeep);
+
+		// the delegation is transitive
+		assert_eq!(Voting::get_effective_vote_amount(1), Some(27));
+		assert_eq!(Voting::get_effective_vote_amount(2), Some(17));
+		assert_eq!(Voting::get_effective_vote_amount(5), None);
+
+		System::set_block_number(2);
+		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options, BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+
+		// the delegate votes with all the delegated votes
+		let vote_vec_1: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:0, votes:27}];
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, vote_vec_1, Conviction::None));
+		assert!(Voting::get_active_proposal(1).unwrap().options[0].votes == 27);
+
+		// the delegator overrides the delegation and votes with its own and delegated votes
+		let vote_vec_2: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:1, votes:17}];
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(2), 1, vote_vec_2, Conviction::None));
+
+		let active_proposal = Voting::get_active_proposal(1).unwrap();
+		assert!(active_proposal.options[0].votes == 10 && active_proposal.options[1].votes == 17);
+	});
+}
+
+#[test]
+fn undelegate_votes() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(2), 64));
+
+		assert_noop!(Voting::undelegate(RuntimeOrigin::signed(2)), Error::<Test>::NotDelegating);
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(2), 1));
+
+		System::set_block_number(2);
+		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options, BallotType::RankedChoice, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(1), 1, bounded_vec![2, 0], Conviction::None));
+		assert!(Voting::get_active_proposal(1).unwrap().options[2].votes == 18);
+
+		// the delegated votes are removed from the delegate ballot
+		assert_ok!(Voting::undelegate(RuntimeOrigin::signed(2)));
+		System::assert_last_event(Event::Undelegated { who: 2, from: 1 }.into());
+		assert!(Voting::get_active_proposal(1).unwrap().options[2].votes == 10);
+		assert_eq!(Voting::get_effective_vote_amount(1), Some(10));
+
+		// the voter can vote with its own votes
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(2), 1, bounded_vec![0], Conviction::None));
+		System::set_block_number(15);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
+		assert_eq!(Voting::get_closed_proposal(1).unwrap().winner_index, 2);
+	});
+}
+
+#[test]
+fn delegation_after_the_vote_is_not_taken_back() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// add voters with 10 and 8 votes
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(2), 64));
+
+		System::set_block_number(2);
+		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options, BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct {id:0, votes:10}], Conviction::None));
+
+		// the delegation made after the vote adds nothing to the ballot and takes nothing back
+		for _ in 0..3 {
+			assert_ok!(Voting::delegate(RuntimeOrigin::signed(2), 1));
+			assert_ok!(Voting::undelegate(RuntimeOrigin::signed(2)));
+		}
+		assert_eq!(Voting::get_active_proposal(1).unwrap().options[0].votes, 10);
+
+		// neither does a direct vote of the delegator
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(2), 1));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(2), 1, bounded_vec![VoteStruct {id:1, votes:8}], Conviction::None));
+		let active_proposal = Voting::get_active_proposal(1).unwrap();
+		assert!(active_proposal.options[0].votes == 10 && active_proposal.options[1].votes == 8);
+	});
+}
+
+#[test]
+fn cannot_unbond_with_unrevealed_ballots() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+
+		System::set_block_number(2);
+		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options, BallotType::Split, true, Thresholds::default(), None, bounded_vec![], None, None, None));
+		assert_ok!(Voting::commit_vote(RuntimeOrigin::signed(1), 1, H256::random()));
+
+		// the committed stake cannot leave the voting reserve before the ballot is slashed
+		assert_noop!(Voting::unbond(RuntimeOrigin::signed(1), 100), Error::<Test>::VotesLocked);
+		System::set_block_number(18);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(2), 1));
+		assert_noop!(Voting::unbond(RuntimeOrigin::signed(1), 100), Error::<Test>::VotesLocked);
+
+		assert_ok!(Voting::slash_unrevealed(RuntimeOrigin::signed(2), 1, 1));
+		System::assert_last_event(Event::UnrevealedVoteSlashed { who: 1, proposal_id: 1, amount: 10 }.into());
+		assert_ok!(Voting::unbond(RuntimeOrigin::signed(1), 90));
+		assert_eq!(Balances::reserved_balance_named(&UnbondingReserveIdValue::get(), &1), 90);
+	});
+}
+
+#[test]
+fn release_asset_votes_releases_only_the_pallet_holds() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		MockAssets::set_balance(GOVERNANCE_ASSET, &1, 400);
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_asset_votes(RuntimeOrigin::signed(1), GOVERNANCE_ASSET, 300));
+
+		// another holder of the asset holds the 100 tokens left of the voter
+		assert_ok!(<MockAssets as frame_support::traits::tokens::fungibles::MutateHold<u64>>::hold(GOVERNANCE_ASSET, &1, 100));
+		assert_ok!(Voting::release_asset_votes(RuntimeOrigin::signed(1), GOVERNANCE_ASSET));
+		System::assert_last_event(Event::AssetReleased { who: 1, asset: GOVERNANCE_ASSET, amount: 300 }.into());
+		assert_eq!(MockAssets::balances(GOVERNANCE_ASSET, &1), (300, 100));
+	});
+}
+
+#[test]
+fn unrevealed_asset_ballot_slashes_the_asset() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
+		MockAssets::set_balance(GOVERNANCE_ASSET, &1, 400);
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::get_asset_votes(RuntimeOrigin::signed(1), GOVERNANCE_ASSET, 400));
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options, BallotType::Split, true, Thresholds::default(), None, bounded_vec![], None, None, Some(GOVERNANCE_ASSET)));
+		assert_ok!(Voting::commit_vote(RuntimeOrigin::signed(1), 1, H256::random()));
+
+		// the tokens stay held until the unrevealed ballot is slashed
+		System::set_block_number(18);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(2), 1));
+		assert_noop!(Voting::release_asset_votes(RuntimeOrigin::signed(1), GOVERNANCE_ASSET), Error::<Test>::VotesLocked);
+
+		// 10% of the held tokens go to the reward pot and the native stake is not touched
+		assert_ok!(Voting::slash_unrevealed(RuntimeOrigin::signed(2), 1, 1));
+		System::assert_last_event(Event::UnrevealedAssetSlashed { who: 1, proposal_id: 1, asset: GOVERNANCE_ASSET, amount: 40 }.into());
+		assert_eq!(MockAssets::balances(GOVERNANCE_ASSET, &Voting::reward_pot()), (40, 0));
+		assert_eq!(Balances::reserved_balance_named(&VotingReserveIdValue::get(), &1), 100);
+
+		assert_ok!(Voting::release_asset_votes(RuntimeOrigin::signed(1), GOVERNANCE_ASSET));
+		assert_eq!(MockAssets::balances(GOVERNANCE_ASSET, &1), (360, 0));
+	});
+}
+
+#[test]
+fn only_the_proposals_a_voter_could_vote_in_are_missed() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
+		// the voter two has no votes
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+
+		// nobody votes in a proposal and in an asset proposal
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		System::set_block_number(13);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, Some(GOVERNANCE_ASSET)));
+		System::set_block_number(24);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 2));
+
+		// the asset proposal breaks the streak
+		assert!(!Voting::is_inactive(&1));
+
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		System::set_block_number(35);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 3));
+		assert!(!Voting::is_inactive(&1));
+
+		// two proposals in a row skipped with votes make the voter one inactive, the voter two
+		// could not vote in them
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options, BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		System::set_block_number(46);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 4));
+		assert!(Voting::is_inactive(&1));
+		assert_noop!(Voting::reap_inactive_voter(RuntimeOrigin::signed(4), 2), Error::<Test>::VoterNotInactive);
+		assert_ok!(Voting::reap_inactive_voter(RuntimeOrigin::signed(4), 1));
+	});
+}
+
+#[test]
+fn ballots_are_cleared_when_the_proposal_closes() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+
+		System::set_block_number(2);
+		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options, BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct {id:0, votes:10}], Conviction::None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 2, bounded_vec![VoteStruct {id:0, votes:10}], Conviction::None));
+		assert!(CastVotes::<Test>::contains_key(1, 1) && CastVotes::<Test>::contains_key(2, 1));
+
+		// by the cancel origin, with the leftover weight of the block
+		assert_ok!(Voting::cancel_proposal(RuntimeOrigin::root(), 2));
+		assert!(crate::ClosedBallots::<Test>::get(2) == Some(true));
+		Voting::on_idle(2, Weight::MAX);
+		assert_eq!(CastVotes::<Test>::iter_prefix_values(2).count(), 0);
+
+		// and after the end of the proposal, with the leftover weight of the block
+		System::set_block_number(13);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
+		assert!(crate::ClosedBallots::<Test>::contains_key(1));
+		Voting::on_idle(13, Weight::MAX);
+		assert_eq!(CastVotes::<Test>::iter_prefix_values(1).count(), 0);
+		assert!(!crate::ClosedBallots::<Test>::contains_key(1));
+	});
+}
+
+#[test]
+fn cancelled_proposal_leaves_no_ballots() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(2), 64));
+
+		System::set_block_number(2);
+		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options, BallotType::Split, true, Thresholds::default(), None, bounded_vec![], None, None, None));
+		assert_ok!(Voting::commit_vote(RuntimeOrigin::signed(1), 1, H256::random()));
+		assert_ok!(Voting::commit_vote(RuntimeOrigin::signed(2), 1, H256::random()));
+
+		assert_ok!(Voting::cancel_proposal(RuntimeOrigin::root(), 1));
+		Voting::on_idle(2, Weight::MAX);
+		assert!(!Commitments::<Test>::contains_key(1, 1) && !Commitments::<Test>::contains_key(2, 1));
+		assert_eq!(VotedProposals::<Test>::iter_prefix_values(1).count(), 0);
+	});
+}
+
+#[test]
+fn queued_proposal_vetoed_before_the_launch() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
+		let text = H256::random();
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::submit_proposal(RuntimeOrigin::signed(1), text, options, BallotType::Split, false, Thresholds::default(), None, bounded_vec![]));
+		assert_ok!(Voting::second(RuntimeOrigin::signed(2), 0));
+		assert_ok!(Voting::veto(RuntimeOrigin::root(), text));
+
+		// the proposal is dropped, the proposer deposit slashed and the seconder refunded
+		System::set_block_number(4);
+		Voting::on_initialize(4);
+		System::assert_has_event(Event::ProposalSlashed { index: 0, who: 1, amount: 20 }.into());
+		System::assert_last_event(Event::LaunchFailed { index: 0, error: Error::<Test>::ProposalVetoed.into() }.into());
+		assert!(Voting::get_active_proposal_ids().is_empty());
+		assert!(crate::ProposalQueue::<Test>::get().is_empty());
+		assert_eq!((Balances::free_balance(1), Balances::reserved_balance(1)), (930, 0));
+		assert_eq!((Balances::free_balance(2), Balances::reserved_balance(2)), (450, 0));
+	});
+}
+
+#[test]
+fn enactments_over_the_weight_limit_wait_for_the_next_block() {
+	use frame_support::dispatch::GetDispatchInfo;
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
+		let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![1] });
+		let call_weight = call.get_dispatch_info().weight;
+		let remark = <Preimage as StorePreimage>::bound(call).unwrap();
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options.clone(), BallotType::Split, false, Thresholds::default(), None, bounded_vec![(1, remark.clone())], None, None, None));
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(),H256::random(), options, BallotType::Split, false, Thresholds::default(), None, bounded_vec![(1, remark)], None, None, None));
+		let vote_vec: BoundedVec<VoteStruct,ConstU32<3>> = bounded_vec![VoteStruct {id:1, votes:10}];
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, vote_vec.clone(), Conviction::None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 2, vote_vec, Conviction::None));
+
+		System::set_block_number(13);
+		Voting::on_initialize(13);
+		Voting::on_idle(13, Weight::MAX);
+		assert_eq!(Enactments::<Test>::get(15).len(), 2);
+
+		// only one call fits in the block, the other one is moved to the next block
+		MaxEnactmentWeightValue::set(call_weight);
+		System::set_block_number(15);
+		Voting::on_initialize(15);
+		System::assert_has_event(Event::ProposalEnacted { id: 1, result: Err(DispatchError::BadOrigin) }.into());
+		System::assert_last_event(Event::ProposalScheduled { id: 2, when: 16 }.into());
+		assert_eq!(Enactments::<Test>::get(16).len(), 1);
+
+		// a call heavier than the limit fails
+		MaxEnactmentWeightValue::set(Weight::zero());
+		System::set_block_number(16);
+		Voting::on_initialize(16);
+		System::assert_last_event(Event::ProposalEnacted { id: 2, result: Err(Error::<Test>::EnactmentTooHeavy.into()) }.into());
+		assert!(Enactments::<Test>::get(16).is_empty());
+	});
+}
+
+#[test]
+fn voters_registered_before_the_checkpoints_keep_their_power_at_the_snapshots() {
+	use crate::{PowerCheckpoints, Voters};
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// voter 1 got its votes before the checkpoints existed
+		Voters::<Test>::insert(1, 10);
+		assert_ok!(Balances::reserve_named(&VotingReserveIdValue::get(), &1, 100));
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(2), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+
+		// the first change seeds the votes it had before
+		System::set_block_number(3);
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 300));
+		assert_eq!(PowerCheckpoints::<Test>::get(1).into_inner(), vec![(0, 10), (3, 20)]);
+
+		// the proposal sees the votes at its snapshot
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct { id: 0, votes: 20 }], Conviction::None), Error::<Test>::NotEnoughVotes);
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct { id: 0, votes: 10 }], Conviction::None));
+	});
+}
+
+#[test]
+fn open_proposal_and_its_voters_are_moved_on_upgrade() {
+	use crate::{migrations::v1::{v0, MigrateToV1}, ActiveProposalIds, ActiveProposals, ProposalCount, Voters};
+	use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		StorageVersion::new(0).put::<Voting>();
+		Voters::<Test>::insert(1, 10);
+		Voters::<Test>::insert(2, 20);
+		Voters::<Test>::insert(3, 5);
+		ProposalCount::<Test>::put(3);
+		let vote_options = BoundedVec::truncate_from(vec![
+			Options { id: 0, votes: 10, text: H256::random() },
+			Options { id: 1, votes: 20, text: H256::random() },
+		]);
+		v0::ActiveProposal::<Test>::put(v0::CurrentProposal::<Test> {
+			id: 2,
+			end_block: 12,
+			status: ProposalStatus::InProgress,
+			text: H256::zero(),
+			options: vote_options,
+		});
+		// voters 1 and 2 voted the open proposal, voter 3 an older one
+		v0::VotedProposals::<Test>::insert(1, 2);
+		v0::VotedProposals::<Test>::insert(2, 2);
+		v0::VotedProposals::<Test>::insert(3, 1);
+
+		MigrateToV1::<Test>::on_runtime_upgrade();
+		assert!(!v0::ActiveProposal::<Test>::exists());
+		assert!(!v0::VotedProposals::<Test>::contains_key(3));
+		assert_eq!(ActiveProposalIds::<Test>::get().into_inner(), vec![2]);
+		let proposal = ActiveProposals::<Test>::get(2).unwrap();
+		assert_eq!((proposal.snapshot_block, proposal.start_block, proposal.end_block), (1, 2, 12));
+		assert_eq!((proposal.voters, proposal.spent_votes), (2, 30));
+		assert_eq!(VotedProposals::<Test>::get(2, 1), Some(10));
+		assert_eq!(VotedProposals::<Test>::get(2, 2), Some(20));
+		assert!(!VotedProposals::<Test>::contains_key(2, 3));
+		assert_eq!(StorageVersion::get::<Voting>(), 1);
+
+		// the moved voters cannot vote again nor change their ballots
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), 2, bounded_vec![VoteStruct { id: 0, votes: 10 }], Conviction::None), Error::<Test>::AlreadyVoted);
+		assert_noop!(Voting::revote(RuntimeOrigin::signed(1), 2, bounded_vec![VoteStruct { id: 0, votes: 10 }], Conviction::None), Error::<Test>::BallotNotStored);
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(3), 2, bounded_vec![VoteStruct { id: 0, votes: 5 }], Conviction::None));
+
+		System::set_block_number(13);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 2));
+		assert_eq!(Voting::get_closed_proposal(2).unwrap().winner_index, 1);
+	});
+}
+
+#[test]
+fn finished_proposals_are_moved_on_upgrade() {
+	use crate::{migrations::v1::{v0, MigrateToV1}, ProposalCount};
+	use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<Voting>();
+		ProposalCount::<Test>::put(3);
+		let vote_options = options(2);
+		v0::FinishedProposals::<Test>::insert(1, v0::FinishedProposal::<Test> {
+			id: 1,
+			text: H256::zero(),
+			end_block: 10,
+			status: ProposalStatus::Finished,
+			options_votes: vote_options.clone(),
+			winner_index: 1,
+		});
+
+		MigrateToV1::<Test>::on_runtime_upgrade();
+		let finished_proposal = Voting::get_closed_proposal(1).unwrap();
+		assert_eq!((finished_proposal.end_block, finished_proposal.status), (10, ProposalStatus::Finished));
+		assert_eq!(finished_proposal.ballot_type, BallotType::Split);
+		assert_eq!(finished_proposal.options_votes, vote_options);
+		assert_eq!(finished_proposal.winner_index, 1);
+		assert!(finished_proposal.rounds.is_empty() && finished_proposal.winners.is_empty());
+		assert!(Voting::get_closed_proposal(2).is_none());
+		assert_eq!(Voting::get_closed_proposals(1, 2).len(), 1);
+	});
+}
+
+#[test]
+fn anonymous_reserves_are_moved_to_named_reserves_on_upgrade() {
+	use crate::{migrations::v2::MoveToNamedReserves, PendingProposal, ProposalQueue, Voters};
+	use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(1).put::<Voting>();
+		// voter 1 reserved the fee, 100 of stake and a queued proposal deposit without names, and
+		// another pallet reserved 30
+		Voters::<Test>::insert(1, 10);
+		assert_ok!(Balances::reserve(&1, 50 + 100 + 20 + 30));
+		// voter 2 seconded the proposal
+		Voters::<Test>::insert(2, 0);
+		assert_ok!(Balances::reserve(&2, 50 + 20));
+		// the reserves of accounts that are not voters are not touched
+		assert_ok!(Balances::reserve(&3, 30));
+		ProposalQueue::<Test>::put(BoundedVec::truncate_from(vec![PendingProposal {
+			index: 0,
+			proposer: 1,
+			deposit: 20,
+			text: H256::zero(),
+			options: BoundedVec::default(),
+			ballot_type: BallotType::Split,
+			secret: false,
+			thresholds: Thresholds::default(),
+			tie_break: TieBreak::Declare,
+			calls: BoundedVec::default(),
+			seconds: bounded_vec![2],
+		}]));
+
+		MoveToNamedReserves::<Test>::on_runtime_upgrade();
+		assert_eq!(Balances::reserved_balance_named(&DepositReserveIdValue::get(), &1), 20);
+		assert_eq!(Balances::reserved_balance_named(&RegisterReserveIdValue::get(), &1), 50);
+		assert_eq!(Balances::reserved_balance_named(&VotingReserveIdValue::get(), &1), 100);
+		assert_eq!(Balances::reserved_balance(1), 200);
+		assert_eq!(Balances::reserved_balance_named(&DepositReserveIdValue::get(), &2), 20);
+		assert_eq!(Balances::reserved_balance_named(&RegisterReserveIdValue::get(), &2), 50);
+		assert_eq!(Balances::reserved_balance_named(&VotingReserveIdValue::get(), &2), 0);
+		assert_eq!(Balances::reserved_balance_named(&VotingReserveIdValue::get(), &3), 0);
+		assert_eq!(Balances::reserved_balance(3), 30);
+		assert_eq!(StorageVersion::get::<Voting>(), 2);
+
+		// the stake can be withdrawn from the named reserves, the reserve of the other pallet stays
+		ProposalQueue::<Test>::kill();
+		assert_ok!(Voting::unbond(RuntimeOrigin::signed(1), 100));
+		assert_eq!(Balances::reserved_balance_named(&VotingReserveIdValue::get(), &1), 0);
+		assert_eq!(Balances::reserved_balance(1), 200);
+	});
+}
+
+#[test]
+fn condorcet_tie_between_unbeaten_options() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(3)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(3), 100));
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::Condorcet, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+
+		// 0 and 1 are preferred by the same votes and both beat 2
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(1), 1, bounded_vec![0, 1, 2], Conviction::None));
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(3), 1, bounded_vec![1, 0, 2], Conviction::None));
+		System::set_block_number(13);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
+
+		let finished_proposal = Voting::get_closed_proposal(1).unwrap();
+		assert_eq!(finished_proposal.status, ProposalStatus::Tied);
+		assert_eq!(finished_proposal.ranking.to_vec(), vec![0, 1, 2]);
+		assert_eq!(finished_proposal.tied.to_vec(), vec![VoteStruct {id:0, votes:10}, VoteStruct {id:1, votes:10}]);
+		System::assert_last_event(Event::ProposalTied { id: 1, options: bounded_vec![0, 1], runoff: None }.into());
+	});
+}
+
+#[test]
+fn condorcet_cycle_with_links_of_the_same_strength_is_tied() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(3)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(3), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(7)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(7), 100));
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::Condorcet, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+
+		// every option beats the next one by 20 to 10, so no link of the cycle is the weakest
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(1), 1, bounded_vec![0, 1, 2], Conviction::None));
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(3), 1, bounded_vec![1, 2, 0], Conviction::None));
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(7), 1, bounded_vec![2, 0, 1], Conviction::None));
+		assert_eq!(PairwiseMatrices::<Test>::get(1).to_vec(), vec![0, 20, 10, 10, 0, 20, 20, 10, 0]);
+		System::set_block_number(13);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
+
+		let finished_proposal = Voting::get_closed_proposal(1).unwrap();
+		assert_eq!(finished_proposal.status, ProposalStatus::Tied);
+		assert_eq!(finished_proposal.tied.to_vec(), vec![VoteStruct {id:0, votes:10}, VoteStruct {id:1, votes:10}, VoteStruct {id:2, votes:10}]);
+		System::assert_last_event(Event::ProposalTied { id: 1, options: bounded_vec![0, 1, 2], runoff: None }.into());
+	});
+}
+
+#[test]
+fn single_transferable_vote_exhausted_ballots() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// add voters with 10, 10, 8 and 8 votes
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(3)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(3), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(2), 64));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(4)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(4), 64));
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::SingleTransferable(2), false, Thresholds::default(), None, bounded_vec![], None, None, None));
+
+		// the ballots rank a single option
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(1), 1, bounded_vec![0], Conviction::None));
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(3), 1, bounded_vec![0], Conviction::None));
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(2), 1, bounded_vec![1], Conviction::None));
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(4), 1, bounded_vec![2], Conviction::None));
+		System::set_block_number(13);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
+
+		// the quota is 36 / 3 + 1 = 13. The surplus of option 0 has nowhere to go, then option 2
+		// is eliminated, the option listed last on the tie, and its ballot is exhausted too
+		let finished_proposal = Voting::get_closed_proposal(1).unwrap();
+		assert_eq!(finished_proposal.winners.to_vec(), vec![0, 1]);
+		assert_eq!(finished_proposal.rounds.to_vec(), vec![
+			RunoffRound::<Test> { tallies: bounded_vec![VoteStruct {id:0, votes:20}, VoteStruct {id:1, votes:8}, VoteStruct {id:2, votes:8}], elected: bounded_vec![0], eliminated: None },
+			RunoffRound::<Test> { tallies: bounded_vec![VoteStruct {id:1, votes:8}, VoteStruct {id:2, votes:8}], elected: bounded_vec![], eliminated: Some(2) },
+			RunoffRound::<Test> { tallies: bounded_vec![VoteStruct {id:1, votes:8}], elected: bounded_vec![1], eliminated: None },
+		]);
+		System::assert_last_event(Event::ProposalFinished { id: 1, winner_index: 0, winner_votes: 20 }.into());
+	});
+}
+
+#[test]
+fn single_transferable_vote_elects_several_options_in_a_round() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// add voters with 10, 10 and 8 votes
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(3)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(3), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(2), 64));
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::SingleTransferable(2), false, Thresholds::default(), None, bounded_vec![], None, None, None));
+
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(1), 1, bounded_vec![0, 2], Conviction::None));
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(3), 1, bounded_vec![1, 2], Conviction::None));
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(2), 1, bounded_vec![2], Conviction::None));
+		System::set_block_number(13);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
+
+		// the quota is 28 / 3 + 1 = 10, options 0 and 1 reach it in the first round
+		let finished_proposal = Voting::get_closed_proposal(1).unwrap();
+		assert_eq!(finished_proposal.winners.to_vec(), vec![0, 1]);
+		assert_eq!(finished_proposal.rounds.to_vec(), vec![
+			RunoffRound::<Test> { tallies: bounded_vec![VoteStruct {id:0, votes:10}, VoteStruct {id:1, votes:10}, VoteStruct {id:2, votes:8}], elected: bounded_vec![0, 1], eliminated: None },
+		]);
+	});
+}
+
+#[test]
+fn instant_runoff_eliminates_the_option_listed_last_on_a_tie() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// add voters with 10, 8 and 8 votes
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(2), 64));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(4)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(4), 64));
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::RankedChoice, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(1), 1, bounded_vec![0], Conviction::None));
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(2), 1, bounded_vec![1, 0], Conviction::None));
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(4), 1, bounded_vec![2, 1], Conviction::None));
+		System::set_block_number(13);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
+
+		// options 1 and 2 are tied for the last place and 2 is eliminated
+		let finished_proposal = Voting::get_closed_proposal(1).unwrap();
+		assert_eq!(finished_proposal.status, ProposalStatus::Finished);
+		assert_eq!(finished_proposal.winner_index, 1);
+		assert_eq!(finished_proposal.rounds.to_vec(), vec![
+			RunoffRound::<Test> { tallies: bounded_vec![VoteStruct {id:0, votes:10}, VoteStruct {id:1, votes:8}, VoteStruct {id:2, votes:8}], elected: bounded_vec![], eliminated: Some(2) },
+			RunoffRound::<Test> { tallies: bounded_vec![VoteStruct {id:0, votes:10}, VoteStruct {id:1, votes:16}], elected: bounded_vec![], eliminated: None },
+		]);
+	});
+}
+
+#[test]
+fn instant_runoff_tie_in_the_last_round() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(3)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(3), 100));
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::RankedChoice, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(1), 1, bounded_vec![0], Conviction::None));
+		assert_ok!(Voting::vote_ranked(RuntimeOrigin::signed(3), 1, bounded_vec![1], Conviction::None));
+		System::set_block_number(13);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
+
+		// the last elimination was between the two leading options, so the proposal is tied
+		let finished_proposal = Voting::get_closed_proposal(1).unwrap();
+		assert_eq!(finished_proposal.status, ProposalStatus::Tied);
+		assert_eq!(finished_proposal.rounds.len(), 3);
+		assert_eq!(finished_proposal.tied.to_vec(), vec![VoteStruct {id:0, votes:10}, VoteStruct {id:1, votes:10}]);
+		System::assert_last_event(Event::ProposalTied { id: 1, options: bounded_vec![0, 1], runoff: None }.into());
+	});
+}
+
+#[test]
+fn reserves_of_other_pallets_are_not_touched() {
+	new_test_ext().execute_with(|| {
+		// a deposit held by another pallet
+		assert_ok!(Balances::reserve(&1, 100));
+
+		// the register fee is paid to the pot and the stake is kept in its own named reserve
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_eq!(Balances::free_balance(Voting::reward_pot()), 50);
+		assert_eq!(Balances::reserved_balance_named(&VotingReserveIdValue::get(), &1), 100);
+		assert_eq!(Balances::reserved_balance(1), 200);
+
+		// the deposit gives no votes
+		assert_eq!(Voting::get_vote_amount(1), Some(10));
+
+		// only the voting reserves are released
+		assert_ok!(Voting::withdraw(RuntimeOrigin::signed(1)));
+		assert_eq!(Balances::reserved_balance(1), 100);
+	});
+}
+
+#[test]
+fn unbond_and_withdraw_unbonded() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let options: BoundedVec<Options<H256>,ConstU32<3>> = bounded_vec![Options {id:0, votes:0, text:H256::random()}, Options {id:1, votes:0, text:H256::random()}, Options{id:2, votes:0, text:H256::random()}];
+		assert_noop!(Voting::unbond(RuntimeOrigin::signed(1), 10), Error::<Test>::NotAVoter);
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 400));
+		assert_noop!(Voting::unbond(RuntimeOrigin::signed(1), 0), Error::<Test>::InvalidTokenAmount);
+		assert_noop!(Voting::unbond(RuntimeOrigin::signed(1), 401), Error::<Test>::InvalidTokenAmount);
+
+		// the stake under a conviction lock cannot be unbonded until the lock expires at block 17
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options, BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct {id:0, votes:20}], Conviction::Locked2x));
+		assert_noop!(Voting::unbond(RuntimeOrigin::signed(1), 1), Error::<Test>::VoteLockNotExpired);
+
+		// the votes are computed with the stake left and the voter stays registered
+		System::set_block_number(18);
+		assert_ok!(Voting::unbond(RuntimeOrigin::signed(1), 300));
+		System::assert_last_event(Event::Unbonded { who: 1, amount: 300, until: 23 }.into());
+		assert_eq!(Voting::get_vote_amount(1), Some(10));
+		System::set_block_number(19);
+		assert_ok!(Voting::unbond(RuntimeOrigin::signed(1), 36));
+		assert_eq!(Voting::get_vote_amount(1), Some(8));
+		assert_noop!(Voting::unbond(RuntimeOrigin::signed(1), 1), Error::<Test>::TooManyUnlockingChunks);
+
+		// the unbonded stake stays reserved until the unbonding period is over
+		assert_eq!(Balances::reserved_balance(1), 400);
+		assert_noop!(Voting::withdraw_unbonded(RuntimeOrigin::signed(1)), Error::<Test>::NothingToWithdraw);
+		System::set_block_number(23);
+		assert_ok!(Voting::withdraw_unbonded(RuntimeOrigin::signed(1)));
+		System::assert_last_event(Event::UnbondedWithdrawn { who: 1, amount: 300 }.into());
+		assert_eq!(Balances::reserved_balance(1), 100);
+		assert_eq!(Unlocking::<Test>::get(1).len(), 1);
+		System::set_block_number(24);
+		assert_ok!(Voting::withdraw_unbonded(RuntimeOrigin::signed(1)));
+		assert_eq!(Balances::reserved_balance(1), 64);
+		assert!(!Unlocking::<Test>::contains_key(1));
+		assert_eq!(Voting::get_vote_amount(1), Some(8));
+	});
+}
+
+#[test]
+fn register_fee_funds_the_reward_pot() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(3)));
+		assert_ok!(Voting::withdraw(RuntimeOrigin::signed(3)));
+		assert_eq!(Balances::free_balance(3), 453);
+		assert_eq!(Balances::free_balance(Voting::reward_pot()), 50);
+	});
+}
+
+#[test]
+fn top_up_rewards() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		// only root can top up the pot, with at least the existential deposit
+		assert_noop!(Voting::top_up_rewards(RuntimeOrigin::signed(1), 60), BadOrigin);
+		assert_noop!(Voting::top_up_rewards(RuntimeOrigin::root(), 0), Error::<Test>::InvalidTokenAmount);
+		assert_ok!(Voting::top_up_rewards(RuntimeOrigin::root(), 60));
+		System::assert_last_event(Event::RewardsToppedUp { amount: 60 }.into());
+		assert_eq!(Balances::free_balance(Voting::reward_pot()), 60);
+	});
+}
+
+#[test]
+fn claim_participation_rewards() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Voting::top_up_rewards(RuntimeOrigin::root(), 60));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(2), 400));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(4)));
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct { id: 0, votes: 10 }], Conviction::None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(2), 1, bounded_vec![VoteStruct { id: 1, votes: 20 }], Conviction::None));
+
+		// the reward is shared once the proposal is finished
+		assert_noop!(Voting::claim_rewards(RuntimeOrigin::signed(1), 1), Error::<Test>::NoReward);
+		System::set_block_number(13);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
+
+		// in proportion to the votes spent by each voter
+		assert_noop!(Voting::claim_rewards(RuntimeOrigin::signed(4), 1), Error::<Test>::NotVoted);
+		assert_ok!(Voting::claim_rewards(RuntimeOrigin::signed(1), 1));
+		System::assert_last_event(Event::RewardClaimed { who: 1, proposal_id: 1, amount: 10 }.into());
+		assert_noop!(Voting::claim_rewards(RuntimeOrigin::signed(1), 1), Error::<Test>::RewardAlreadyClaimed);
+		assert_ok!(Voting::claim_rewards(RuntimeOrigin::signed(2), 1));
+		System::assert_last_event(Event::RewardClaimed { who: 2, proposal_id: 1, amount: 20 }.into());
+		assert_eq!(Balances::free_balance(Voting::reward_pot()), 180);
+	});
+}
+
+#[test]
+fn missing_one_proposal_is_not_inactivity() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(2), 100));
+
+		// only the voter one votes in the first proposal
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct { id: 0, votes: 10 }], Conviction::None));
+		System::set_block_number(13);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
+		assert!(!Voting::is_inactive(&2));
+		assert_noop!(Voting::reap_inactive_voter(RuntimeOrigin::signed(4), 2), Error::<Test>::VoterNotInactive);
+	});
+}
+
+#[test]
+fn active_and_new_voters_are_not_reaped() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(2), 100));
+
+		// only the voter one votes in the first proposal
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct { id: 0, votes: 10 }], Conviction::None));
+		System::set_block_number(13);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
+
+		// nobody votes in the second one
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(3)));
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		System::set_block_number(24);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 2));
+		assert!(Voting::is_inactive(&2));
+
+		// the voter one took part and the voter three registered after the first proposal
+		assert_noop!(Voting::reap_inactive_voter(RuntimeOrigin::signed(4), 1), Error::<Test>::VoterNotInactive);
+		assert_noop!(Voting::reap_inactive_voter(RuntimeOrigin::signed(4), 3), Error::<Test>::VoterNotInactive);
+	});
+}
+
+#[test]
+fn reap_inactive_voter() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(2), 100));
+
+		// only the voter one votes in the first proposal
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct { id: 0, votes: 10 }], Conviction::None));
+		System::set_block_number(13);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
+
+		// nobody votes in the second one
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(3)));
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		System::set_block_number(24);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 2));
+
+		// the voter two skipped both and is removed, a fifth of the register fee is slashed from
+		// the stake to the pot and the rest of the stake is refunded
+		assert_ok!(Voting::reap_inactive_voter(RuntimeOrigin::signed(4), 2));
+		System::assert_last_event(Event::InactiveVoterReaped { who: 2, slashed: 10 }.into());
+		assert!(Voting::is_voter(2).is_none());
+		assert_eq!((Balances::free_balance(2), Balances::reserved_balance(2)), (440, 0));
+		assert_eq!(Balances::free_balance(Voting::reward_pot()), 160);
+		assert_noop!(Voting::reap_inactive_voter(RuntimeOrigin::signed(4), 2), Error::<Test>::NotAVoter);
+	});
+}
+
+#[test]
+fn asset_proposal_needs_an_existing_asset() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, Some(2)), Error::<Test>::UnknownAsset);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, Some(GOVERNANCE_ASSET)));
+	});
+}
+
+#[test]
+fn asset_votes_come_from_the_held_tokens() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		MockAssets::set_balance(GOVERNANCE_ASSET, &1, 400);
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(2), 100));
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(2), 1));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(3)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(3), 100));
+
+		// the voters hold tokens of the asset to get votes
+		assert_noop!(Voting::get_asset_votes(RuntimeOrigin::signed(5), GOVERNANCE_ASSET, 100), Error::<Test>::NotAVoter);
+		assert_noop!(Voting::get_asset_votes(RuntimeOrigin::signed(1), GOVERNANCE_ASSET, 500), DispatchError::Token(TokenError::NoFunds));
+		assert_ok!(Voting::get_asset_votes(RuntimeOrigin::signed(1), GOVERNANCE_ASSET, 400));
+		System::assert_last_event(Event::AssetHeld { who: 1, asset: GOVERNANCE_ASSET, amount: 400 }.into());
+		assert_eq!(MockAssets::balances(GOVERNANCE_ASSET, &1), (0, 400));
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, Some(GOVERNANCE_ASSET)));
+
+		// the native stake gives no votes in it
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(3), 1, bounded_vec![VoteStruct { id: 0, votes: 1 }], Conviction::None), Error::<Test>::NotEnoughVotes);
+
+		// without conviction and without the delegated votes
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct { id: 0, votes: 20 }], Conviction::Locked2x), Error::<Test>::ConvictionNotAllowed);
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct { id: 0, votes: 21 }], Conviction::None), Error::<Test>::NotEnoughVotes);
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct { id: 0, votes: 20 }], Conviction::None));
+	});
+}
+
+#[test]
+fn asset_votes_are_released_after_the_proposal() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		MockAssets::set_balance(GOVERNANCE_ASSET, &1, 400);
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(2), 100));
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(2), 1));
+		assert_ok!(Voting::get_asset_votes(RuntimeOrigin::signed(1), GOVERNANCE_ASSET, 400));
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, Some(GOVERNANCE_ASSET)));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct { id: 0, votes: 20 }], Conviction::None));
+
+		// the tokens stay held until the proposal ends
+		assert_noop!(Voting::release_asset_votes(RuntimeOrigin::signed(1), GOVERNANCE_ASSET), Error::<Test>::VotesLocked);
+		System::set_block_number(13);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
+		assert_ok!(Voting::release_asset_votes(RuntimeOrigin::signed(1), GOVERNANCE_ASSET));
+		System::assert_last_event(Event::AssetReleased { who: 1, asset: GOVERNANCE_ASSET, amount: 400 }.into());
+		assert_eq!(MockAssets::balances(GOVERNANCE_ASSET, &1), (400, 0));
+		assert_noop!(Voting::release_asset_votes(RuntimeOrigin::signed(1), GOVERNANCE_ASSET), Error::<Test>::NoAssetStake);
+	});
+}
+
+#[test]
+fn overflowing_split_ballot_is_rejected() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+
+		// the votes of the ballot do not wrap around past the available votes
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct {id:0, votes:u128::MAX}, VoteStruct {id:1, votes:2}], Conviction::None), DispatchError::Arithmetic(ArithmeticError::Overflow));
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct {id:0, votes:u128::MAX}, VoteStruct {id:0, votes:2}], Conviction::None), DispatchError::Arithmetic(ArithmeticError::Overflow));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct {id:0, votes:8}, VoteStruct {id:1, votes:2}], Conviction::None));
+	});
+}
+
+#[test]
+fn power_changed_before_a_proposal_in_the_same_block() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+
+		// the power is topped up with no open proposal, then a proposal is created in the same block
+		System::set_block_number(5);
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 300));
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		assert_eq!(crate::PowerCheckpoints::<Test>::get(1).into_inner(), vec![(1, 10), (5, 20)]);
+
+		// the proposal uses the power before the top-up
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct {id:0, votes:11}], Conviction::None), Error::<Test>::NotEnoughVotes);
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct {id:0, votes:10}], Conviction::None));
+	});
+}
+
+#[test]
+fn conviction_does_not_lock_the_free_balance_while_the_stake_is_reserved() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct {id:0, votes:30}], Conviction::Locked3x));
+
+		// the stake is kept by the reserve, the free balance unrelated to it can be spent
+		assert_eq!(Balances::free_balance(1), 850);
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(1), 2, 800));
+		assert!(Balances::locks(1).is_empty());
+
+		// the stake is locked once it is unreserved by the withdraw
+		System::set_block_number(13);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
+		assert_ok!(Voting::withdraw(RuntimeOrigin::signed(1)));
+		assert_eq!(Balances::locks(1).iter().map(|lock| lock.amount).collect::<Vec<_>>(), vec![100]);
+		assert_noop!(Balances::transfer(RuntimeOrigin::signed(1), 2, 101), BalanceError::LiquidityRestrictions);
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(1), 2, 100));
+	});
+}
+
+#[test]
+fn deposits_are_refunded_when_the_launch_fails() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::submit_proposal(RuntimeOrigin::signed(1), H256::random(), options(3), BallotType::Split, false, Thresholds::default(), None, bounded_vec![]));
+		assert_ok!(Voting::second(RuntimeOrigin::signed(2), 0));
+
+		// the default duration is out of bounds, so the proposal cannot be launched
+		MaxProposalDurationValue::set(1);
+		System::set_block_number(4);
+		Voting::on_initialize(4);
+		System::assert_last_event(Event::LaunchFailed { index: 0, error: Error::<Test>::InvalidDuration.into() }.into());
+		assert!(Voting::get_active_proposal_ids().is_empty());
+		assert!(crate::ProposalQueue::<Test>::get().is_empty());
+
+		// the proposal is dropped and both deposits are refunded
+		assert_eq!((Balances::free_balance(1), Balances::reserved_balance(1)), (950, 0));
+		assert_eq!((Balances::free_balance(2), Balances::reserved_balance(2)), (450, 0));
+		MaxProposalDurationValue::set(10);
+	});
+}
+
+#[test]
+fn reward_is_capped_by_the_pot_when_the_proposal_closes() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(1), 100));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::get_votes(RuntimeOrigin::signed(2), 400));
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct { id: 0, votes: 10 }], Conviction::None));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(2), 1, bounded_vec![VoteStruct { id: 1, votes: 20 }], Conviction::None));
+
+		// the pot has less than the reward when the proposal closes
+		Balances::make_free_balance_be(&Voting::reward_pot(), 21);
+		System::set_block_number(13);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
+
+		// the shares come from the funds over the existential deposit, which stays in the pot
+		assert_ok!(Voting::claim_rewards(RuntimeOrigin::signed(1), 1));
+		System::assert_last_event(Event::RewardClaimed { who: 1, proposal_id: 1, amount: 6 }.into());
+		assert_ok!(Voting::claim_rewards(RuntimeOrigin::signed(2), 1));
+		System::assert_last_event(Event::RewardClaimed { who: 2, proposal_id: 1, amount: 13 }.into());
+		assert_eq!(Balances::free_balance(Voting::reward_pot()), 2);
+	});
+}
+
+#[test]
+fn asset_votes_are_taken_at_the_snapshot_block() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		MockAssets::set_balance(GOVERNANCE_ASSET, &1, 400);
+		MockAssets::set_balance(GOVERNANCE_ASSET, &2, 400);
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(1)));
+		assert_ok!(Voting::add_voter(RuntimeOrigin::signed(2)));
+		assert_ok!(Voting::get_asset_votes(RuntimeOrigin::signed(1), GOVERNANCE_ASSET, 400));
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, Some(GOVERNANCE_ASSET)));
+
+		// the tokens held after the snapshot give no votes in the proposal
+		assert_ok!(Voting::get_asset_votes(RuntimeOrigin::signed(2), GOVERNANCE_ASSET, 400));
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(2), 1, bounded_vec![VoteStruct { id: 0, votes: 1 }], Conviction::None), Error::<Test>::NotEnoughVotes);
+
+		// the tokens released after the snapshot do not back a ballot
+		assert_ok!(Voting::release_asset_votes(RuntimeOrigin::signed(1), GOVERNANCE_ASSET));
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct { id: 0, votes: 1 }], Conviction::None), Error::<Test>::NotEnoughVotes);
+		assert_ok!(Voting::get_asset_votes(RuntimeOrigin::signed(1), GOVERNANCE_ASSET, 100));
+		assert_noop!(Voting::vote(RuntimeOrigin::signed(1), 1, bounded_vec![VoteStruct { id: 0, votes: 11 }], Conviction::None), Error::<Test>::NotEnoughVotes);
+	});
+}
+
+#[test]
+fn delegators_through_a_chain_are_bounded() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for voter in 1..=5 {
+			assert_ok!(Voting::add_voter(RuntimeOrigin::signed(voter)));
+		}
+
+		// 4 -> 2 -> 1 <- 3
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(2), 1));
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(3), 1));
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(4), 2));
+		assert_eq!(crate::DelegatorCount::<Test>::get(1), 3);
+		assert_eq!(crate::DelegatorCount::<Test>::get(2), 1);
+
+		// the voter three has room for direct delegators but the voter one is full
+		assert_noop!(Voting::delegate(RuntimeOrigin::signed(5), 3), Error::<Test>::TooManyDelegators);
+
+		// the delegators of a voter leave the chain with it
+		assert_ok!(Voting::undelegate(RuntimeOrigin::signed(2)));
+		assert_eq!(crate::DelegatorCount::<Test>::get(1), 1);
+		assert_eq!(crate::DelegatorCount::<Test>::get(2), 1);
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(5), 3));
+		assert_eq!(crate::DelegatorCount::<Test>::get(1), 2);
+
+		// and a withdrawn voter leaves no count behind
+		assert_ok!(Voting::withdraw(RuntimeOrigin::signed(2)));
+		assert_eq!(crate::DelegatorCount::<Test>::get(2), 0);
+		assert!(Voting::is_voter(4).is_some());
+	});
+}
+
+#[test]
+fn ballots_of_closed_proposals_are_removed_in_batches() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		for voter in 1..=3 {
+			assert_ok!(Voting::add_voter(RuntimeOrigin::signed(voter)));
+			assert_ok!(Voting::get_votes(RuntimeOrigin::signed(voter), 100));
+		}
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::Split, false, Thresholds::default(), None, bounded_vec![], None, None, None));
+		for voter in 1..=3 {
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(voter), 1, bounded_vec![VoteStruct { id: 0, votes: 10 }], Conviction::None));
+		}
+		System::set_block_number(13);
+		assert_ok!(Voting::end_proposal(RuntimeOrigin::signed(1), 1));
+	});
+	// the ballots are in the backend, as in a block after the one that cast them
+	ext.commit_all().unwrap();
+	ext.execute_with(|| {
+		// `MaxBallotRemovalsPerBlock` ballots are removed in every block
+		Voting::on_idle(14, Weight::MAX);
+		assert_eq!(CastVotes::<Test>::iter_prefix_values(1).count(), 1);
+		assert!(crate::ClosedBallots::<Test>::contains_key(1));
+	});
+	ext.commit_all().unwrap();
+	ext.execute_with(|| {
+		Voting::on_idle(15, Weight::MAX);
+		assert_eq!(CastVotes::<Test>::iter_prefix_values(1).count(), 0);
+		assert!(!crate::ClosedBallots::<Test>::contains_key(1));
+	});
+}
+
+#[test]
+fn commitments_of_cancelled_proposals_are_removed_in_batches() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		for voter in 1..=2 {
+			assert_ok!(Voting::add_voter(RuntimeOrigin::signed(voter)));
+			assert_ok!(Voting::get_votes(RuntimeOrigin::signed(voter), 100));
+		}
+		System::set_block_number(2);
+		assert_ok!(Voting::set_proposal(RuntimeOrigin::root(), H256::random(), options(3), BallotType::Split, true, Thresholds::default(), None, bounded_vec![], None, None, None));
+		for voter in 1..=2 {
+			assert_ok!(Voting::commit_vote(RuntimeOrigin::signed(voter), 1, H256::random()));
+		}
+		assert_ok!(Voting::cancel_proposal(RuntimeOrigin::root(), 1));
+
+		// a voter with a commitment to a cancelled proposal can withdraw before it is removed
+		assert_ok!(Voting::withdraw(RuntimeOrigin::signed(2)));
+
+		// the commitment and the voted proposals entry of a voter take two removals
+		Voting::on_idle(2, Weight::MAX);
+		assert_eq!(VotedProposals::<Test>::iter_prefix_values(1).count(), 1);
+		assert!(crate::ClosedBallots::<Test>::contains_key(1));
+		Voting::on_idle(3, Weight::MAX);
+		assert_eq!(VotedProposals::<Test>::iter_prefix_values(1).count(), 0);
+		assert!(!Commitments::<Test>::contains_key(1, 1) && !Commitments::<Test>::contains_key(2, 1));
+		assert!(!crate::ClosedBallots::<Test>::contains_key(1));
+	});
+}
@@ -1,24 +1,13 @@
-
-//! Autogenerated weights for `pallet_voting`
+//! Weights for `pallet_voting`.
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-01-29, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! HOSTNAME: `Mateo`, CPU: `Intel(R) Core(TM) i5-1035G4 CPU @ 1.10GHz`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
-
-// Executed Command:
-// target/release/node-template
-// benchmark
-// pallet
-// --chain=dev
-// --steps=50
-// --repeat=20
-// --pallet=pallet-voting
-// --extrinsic=*
-// --execution=wasm
-// --wasm-execution=compiled
-// --heap-pages=4096
-// --output=./pallets/voting/src/weights.rs
+//! The weights are estimates written by hand from the storage accessed by every call, with no
+//! delegators. Every delegator walked by a call, and every ballot of a closed proposal removed on
+//! idle, is charged on top by the pallet. Regenerate them with the benchmark CLI of the node before
+//! using the pallet in production:
+//!
+//! target/release/node-template benchmark pallet --chain=dev --steps=50 --repeat=20
+//! --pallet=pallet-voting --extrinsic=* --execution=wasm --wasm-execution=compiled
+//! --heap-pages=4096 --output=./pallets/voting/src/weights.rs
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
@@ -32,58 +21,488 @@ pub trait WeightInfo {
 	fn get_votes() -> Weight;
 	fn set_proposal() -> Weight;
 	fn vote() -> Weight;
-	fn end_proposal() -> Weight;
+	fn end_proposal(o: u32, r: u32, ) -> Weight;
 	fn withdraw() -> Weight;
+	fn vote_ranked() -> Weight;
+	fn commit_vote() -> Weight;
+	fn reveal_vote() -> Weight;
+	fn reveal_ranked() -> Weight;
+	fn slash_unrevealed() -> Weight;
+	fn delegate() -> Weight;
+	fn undelegate() -> Weight;
+	fn resolve_tie() -> Weight;
+	fn unvote() -> Weight;
+	fn revote() -> Weight;
+	fn revote_ranked() -> Weight;
+	fn unlock() -> Weight;
+	fn submit_proposal() -> Weight;
+	fn cancel_queued_proposal() -> Weight;
+	fn second() -> Weight;
+	fn cancel_proposal() -> Weight;
+	fn veto() -> Weight;
+	fn unbond() -> Weight;
+	fn withdraw_unbonded() -> Weight;
+	fn top_up_rewards() -> Weight;
+	fn claim_rewards() -> Weight;
+	fn reap_inactive_voter() -> Weight;
+	fn get_asset_votes() -> Weight;
+	fn release_asset_votes() -> Weight;
 }
 
 /// Weight functions for `pallet_voting`.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	// Storage: Voting Voters (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Voting ProposalCount (r:1 w:0)
+	// Storage: Voting VoterSince (r:0 w:1)
 	fn add_voter() -> Weight {
-		// Minimum execution time: 44_830 nanoseconds.
 		Weight::from_ref_time(45_963_000)
-			.saturating_add(T::DbWeight::get().reads(1))
-			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(4))
 	}
 	// Storage: Voting Voters (r:1 w:1)
+	// Storage: Voting ActiveProposalIds (r:1 w:0)
+	// Storage: Voting ActiveProposals (r:1 w:0)
+	// Storage: Voting PowerCheckpoints (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: Voting RecentProposals (r:1 w:0)
 	fn get_votes() -> Weight {
-		// Minimum execution time: 49_383 nanoseconds.
 		Weight::from_ref_time(51_324_000)
-			.saturating_add(T::DbWeight::get().reads(1))
-			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(3))
 	}
-	// Storage: Voting ActiveProposal (r:1 w:1)
 	// Storage: Voting ProposalCount (r:1 w:1)
+	// Storage: Voting ActiveProposalIds (r:1 w:1)
+	// Storage: Voting ActiveProposals (r:0 w:1)
+	// Storage: Voting OptionCalls (r:0 w:1)
+	// Storage: Voting ProposalAssets (r:0 w:1)
 	fn set_proposal() -> Weight {
-		// Minimum execution time: 31_415 nanoseconds.
-		Weight::from_ref_time(32_145_000)
+		Weight::from_ref_time(34_710_000)
 			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(2))
+			.saturating_add(T::DbWeight::get().writes(5))
 	}
 	// Storage: Voting Voters (r:1 w:0)
-	// Storage: Voting ActiveProposal (r:1 w:1)
+	// Storage: Voting ActiveProposals (r:1 w:1)
+	// Storage: Voting ProposalAssets (r:1 w:0)
+	// Storage: Voting PowerCheckpoints (r:1 w:0)
+	// Storage: Voting Delegators (r:1 w:0)
 	// Storage: Voting VotedProposals (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:0)
+	// Storage: Voting VoteLocks (r:1 w:1)
+	// Storage: Voting DelegatedVotes (r:1 w:1)
+	// Storage: Voting Delegations (r:1 w:0)
+	// Storage: Voting CastVotes (r:0 w:1)
 	fn vote() -> Weight {
-		// Minimum execution time: 50_141 nanoseconds.
 		Weight::from_ref_time(53_487_000)
-			.saturating_add(T::DbWeight::get().reads(3))
-			.saturating_add(T::DbWeight::get().writes(2))
+			.saturating_add(T::DbWeight::get().reads(10))
+			.saturating_add(T::DbWeight::get().writes(5))
 	}
-	// Storage: Voting ActiveProposal (r:1 w:1)
+	// Storage: Voting ActiveProposals (r:1 w:1)
+	// Storage: Voting ActiveProposalIds (r:1 w:1)
 	// Storage: Voting FinishedProposals (r:0 w:1)
-	fn end_proposal() -> Weight {
-		// Minimum execution time: 35_472 nanoseconds.
-		Weight::from_ref_time(37_081_000)
-			.saturating_add(T::DbWeight::get().reads(1))
-			.saturating_add(T::DbWeight::get().writes(2))
+	// Storage: Voting OptionCalls (r:1 w:1)
+	// Storage: Voting Enactments (r:1 w:1)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: Voting ProposalCount (r:1 w:1)
+	// Storage: Voting ProposalRewards (r:0 w:1)
+	// Storage: System Account (r:1 w:0)
+	// Storage: Voting RecentProposals (r:1 w:1)
+	// Storage: Voting ProposalAssets (r:1 w:0)
+	// Storage: Voting ClosedBallots (r:0 w:1)
+	// Storage: Preimage StatusFor (r:1 w:1)
+	// Storage: Voting RankedBallots (r:1 w:1)
+	// Storage: Voting PairwiseMatrices (r:1 w:1)
+	/// The range of component `o` is `[2, 3]`.
+	/// The range of component `r` is `[0, 64]`.
+	fn end_proposal(o: u32, r: u32, ) -> Weight {
+		Weight::from_ref_time(41_530_000)
+			.saturating_add(Weight::from_ref_time(3_160_000).saturating_mul(o.into()))
+			.saturating_add(Weight::from_ref_time(1_214_000).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(12))
+			.saturating_add(T::DbWeight::get().writes(12))
 	}
 	// Storage: Voting Voters (r:1 w:1)
-	// Storage: Voting ActiveProposal (r:1 w:0)
+	// Storage: Voting ActiveProposalIds (r:1 w:0)
+	// Storage: Voting ActiveProposals (r:1 w:0)
+	// Storage: Voting VotedProposals (r:1 w:0)
+	// Storage: Voting Commitments (r:1 w:0)
+	// Storage: Voting Delegations (r:1 w:0)
+	// Storage: Voting Delegators (r:1 w:1)
+	// Storage: Voting DelegatorCount (r:0 w:1)
+	// Storage: Voting VoterSince (r:0 w:1)
+	// Storage: Voting PowerCheckpoints (r:1 w:1)
+	// Storage: Voting RecentProposals (r:1 w:0)
+	// Storage: Voting VoteLocks (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
 	fn withdraw() -> Weight {
-		// Minimum execution time: 50_598 nanoseconds.
 		Weight::from_ref_time(51_795_000)
+			.saturating_add(T::DbWeight::get().reads(13))
+			.saturating_add(T::DbWeight::get().writes(9))
+	}
+	// Storage: Voting Voters (r:1 w:0)
+	// Storage: Voting ActiveProposals (r:1 w:1)
+	// Storage: Voting ProposalAssets (r:1 w:0)
+	// Storage: Voting PowerCheckpoints (r:1 w:0)
+	// Storage: Voting Delegators (r:1 w:0)
+	// Storage: Voting VotedProposals (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:0)
+	// Storage: Voting VoteLocks (r:1 w:1)
+	// Storage: Voting DelegatedVotes (r:1 w:1)
+	// Storage: Voting Delegations (r:1 w:0)
+	// Storage: Voting CastVotes (r:0 w:1)
+	// Storage: Voting RankedBallots (r:1 w:1)
+	fn vote_ranked() -> Weight {
+		Weight::from_ref_time(60_932_000)
+			.saturating_add(T::DbWeight::get().reads(11))
+			.saturating_add(T::DbWeight::get().writes(6))
+	}
+	// Storage: Voting Voters (r:1 w:0)
+	// Storage: Voting ActiveProposals (r:1 w:1)
+	// Storage: Voting VotedProposals (r:1 w:1)
+	// Storage: Voting Commitments (r:0 w:1)
+	// Storage: Voting ProposalAssets (r:1 w:0)
+	fn commit_vote() -> Weight {
+		Weight::from_ref_time(42_310_000)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Storage: Voting ActiveProposals (r:1 w:1)
+	// Storage: Voting Commitments (r:1 w:1)
+	// Storage: Voting VotedProposals (r:1 w:1)
+	fn reveal_vote() -> Weight {
+		Weight::from_ref_time(57_960_000)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Storage: Voting ActiveProposals (r:1 w:1)
+	// Storage: Voting Commitments (r:1 w:1)
+	// Storage: Voting VotedProposals (r:1 w:1)
+	// Storage: Voting RankedBallots (r:1 w:1)
+	fn reveal_ranked() -> Weight {
+		Weight::from_ref_time(64_403_000)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	// Storage: Voting FinishedProposals (r:1 w:0)
+	// Storage: Voting Commitments (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting Voters (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: Voting VotedProposals (r:0 w:1)
+	// Storage: Voting ProposalAssets (r:1 w:0)
+	fn slash_unrevealed() -> Weight {
+		Weight::from_ref_time(62_018_000)
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	// Storage: Voting Voters (r:2 w:0)
+	// Storage: Voting Delegations (r:2 w:1)
+	// Storage: Voting Delegators (r:2 w:1)
+	// Storage: Voting DelegatorCount (r:2 w:1)
+	fn delegate() -> Weight {
+		Weight::from_ref_time(48_139_000)
+			.saturating_add(T::DbWeight::get().reads(8))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Storage: Voting Delegations (r:2 w:1)
+	// Storage: Voting ActiveProposalIds (r:1 w:0)
+	// Storage: Voting ActiveProposals (r:1 w:1)
+	// Storage: Voting VotedProposals (r:2 w:1)
+	// Storage: Voting Voters (r:1 w:0)
+	// Storage: Voting Delegators (r:2 w:1)
+	// Storage: Voting CastVotes (r:1 w:1)
+	// Storage: Voting DelegatedVotes (r:1 w:1)
+	// Storage: Voting ProposalAssets (r:1 w:0)
+	// Storage: Voting DelegatorCount (r:1 w:1)
+	fn undelegate() -> Weight {
+		Weight::from_ref_time(73_842_000)
+			.saturating_add(T::DbWeight::get().reads(13))
+			.saturating_add(T::DbWeight::get().writes(7))
+	}
+	// Storage: Voting FinishedProposals (r:1 w:1)
+	// Storage: Voting OptionCalls (r:1 w:1)
+	// Storage: Voting Enactments (r:1 w:1)
+	// Storage: Preimage StatusFor (r:1 w:1)
+	fn resolve_tie() -> Weight {
+		Weight::from_ref_time(34_902_000)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	// Storage: Voting Voters (r:1 w:0)
+	// Storage: Voting ActiveProposals (r:1 w:1)
+	// Storage: Voting VotedProposals (r:1 w:1)
+	// Storage: Voting CastVotes (r:1 w:1)
+	// Storage: Voting RankedBallots (r:1 w:1)
+	fn unvote() -> Weight {
+		Weight::from_ref_time(42_830_000)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	// Storage: Voting Voters (r:1 w:0)
+	// Storage: Voting ActiveProposals (r:1 w:1)
+	// Storage: Voting VotedProposals (r:1 w:1)
+	// Storage: Voting CastVotes (r:1 w:1)
+	// Storage: Voting ProposalAssets (r:1 w:0)
+	// Storage: Voting PowerCheckpoints (r:1 w:0)
+	// Storage: Voting Delegators (r:1 w:0)
+	// Storage: Balances Reserves (r:1 w:0)
+	// Storage: Voting VoteLocks (r:1 w:1)
+	fn revote() -> Weight {
+		Weight::from_ref_time(60_372_000)
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	// Storage: Voting Voters (r:1 w:0)
+	// Storage: Voting ActiveProposals (r:1 w:1)
+	// Storage: Voting VotedProposals (r:1 w:1)
+	// Storage: Voting CastVotes (r:1 w:1)
+	// Storage: Voting ProposalAssets (r:1 w:0)
+	// Storage: Voting PowerCheckpoints (r:1 w:0)
+	// Storage: Voting Delegators (r:1 w:0)
+	// Storage: Balances Reserves (r:1 w:0)
+	// Storage: Voting VoteLocks (r:1 w:1)
+	// Storage: Voting RankedBallots (r:1 w:1)
+	fn revote_ranked() -> Weight {
+		Weight::from_ref_time(65_119_000)
+			.saturating_add(T::DbWeight::get().reads(10))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	// Storage: Voting VoteLocks (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn unlock() -> Weight {
+		Weight::from_ref_time(32_417_000)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Storage: Voting Voters (r:1 w:0)
+	// Storage: Voting SubmittedProposalCount (r:1 w:1)
+	// Storage: Voting ProposalQueue (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	fn submit_proposal() -> Weight {
+		Weight::from_ref_time(41_270_000)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	// Storage: Voting ProposalQueue (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	fn cancel_queued_proposal() -> Weight {
+		Weight::from_ref_time(36_952_000)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Storage: Voting Voters (r:1 w:0)
+	// Storage: Voting ProposalQueue (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	fn second() -> Weight {
+		Weight::from_ref_time(34_660_000)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Storage: Voting ActiveProposals (r:1 w:1)
+	// Storage: Voting ActiveProposalIds (r:1 w:1)
+	// Storage: Voting OptionCalls (r:0 w:1)
+	// Storage: Voting RankedBallots (r:0 w:1)
+	// Storage: Voting FinishedProposals (r:0 w:1)
+	// Storage: Voting ProposalAssets (r:0 w:1)
+	// Storage: Voting PairwiseMatrices (r:0 w:1)
+	// Storage: Voting ClosedBallots (r:0 w:1)
+	fn cancel_proposal() -> Weight {
+		Weight::from_ref_time(31_508_000)
 			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(8))
+	}
+	// Storage: Voting Blacklist (r:0 w:1)
+	fn veto() -> Weight {
+		Weight::from_ref_time(15_611_000)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: Voting Voters (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: Voting VoteLocks (r:1 w:0)
+	// Storage: Voting Unlocking (r:1 w:1)
+	// Storage: Voting ActiveProposalIds (r:1 w:0)
+	// Storage: Voting ActiveProposals (r:1 w:0)
+	// Storage: Voting PowerCheckpoints (r:1 w:1)
+	// Storage: Voting Commitments (r:1 w:0)
+	// Storage: Voting RecentProposals (r:1 w:0)
+	fn unbond() -> Weight {
+		Weight::from_ref_time(53_904_000)
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	// Storage: Voting Unlocking (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: Voting VoteLocks (r:1 w:0)
+	// Storage: Balances Locks (r:1 w:1)
+	fn withdraw_unbonded() -> Weight {
+		Weight::from_ref_time(28_733_000)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Storage: System Account (r:1 w:1)
+	fn top_up_rewards() -> Weight {
+		Weight::from_ref_time(20_318_000)
+			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	// Storage: Voting ProposalRewards (r:1 w:0)
+	// Storage: Voting ClaimedRewards (r:1 w:1)
+	// Storage: Voting Commitments (r:1 w:0)
+	// Storage: Voting VotedProposals (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn claim_rewards() -> Weight {
+		Weight::from_ref_time(46_105_000)
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Storage: Voting Voters (r:1 w:1)
+	// Storage: Voting RecentProposals (r:1 w:0)
+	// Storage: Voting VoterSince (r:1 w:1)
+	// Storage: Voting VotedProposals (r:2 w:0)
+	// Storage: Voting ProposalAssets (r:2 w:0)
+	// Storage: Voting ActiveProposalIds (r:1 w:0)
+	// Storage: Voting ActiveProposals (r:1 w:0)
+	// Storage: Voting Commitments (r:1 w:0)
+	// Storage: Voting Delegations (r:1 w:0)
+	// Storage: Voting Delegators (r:1 w:1)
+	// Storage: Voting DelegatorCount (r:0 w:1)
+	// Storage: Voting PowerCheckpoints (r:1 w:1)
+	// Storage: Voting VoteLocks (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn reap_inactive_voter() -> Weight {
+		Weight::from_ref_time(73_418_000)
+			.saturating_add(T::DbWeight::get().reads(18))
+			.saturating_add(T::DbWeight::get().writes(10))
+	}
+	// Storage: Voting Voters (r:1 w:0)
+	// Storage: Assets Account (r:1 w:1)
+	// Storage: Voting AssetStakes (r:1 w:1)
+	// Storage: Voting ActiveProposalIds (r:1 w:0)
+	// Storage: Voting ActiveProposals (r:1 w:0)
+	// Storage: Voting RecentProposals (r:1 w:0)
+	// Storage: Voting AssetPowerCheckpoints (r:1 w:1)
+	fn get_asset_votes() -> Weight {
+		Weight::from_ref_time(39_847_000)
+			.saturating_add(T::DbWeight::get().reads(7))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Storage: Voting AssetStakes (r:1 w:1)
+	// Storage: Voting ActiveProposalIds (r:1 w:0)
+	// Storage: Voting ProposalAssets (r:1 w:0)
+	// Storage: Voting VotedProposals (r:1 w:0)
+	// Storage: Assets Account (r:1 w:1)
+	// Storage: Voting Commitments (r:1 w:0)
+	// Storage: Voting ActiveProposals (r:1 w:0)
+	// Storage: Voting RecentProposals (r:1 w:0)
+	// Storage: Voting AssetPowerCheckpoints (r:1 w:1)
+	fn release_asset_votes() -> Weight {
+		Weight::from_ref_time(41_652_000)
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn add_voter() -> Weight {
+		Weight::from_ref_time(45_963_000)
+	}
+	fn get_votes() -> Weight {
+		Weight::from_ref_time(51_324_000)
+	}
+	fn set_proposal() -> Weight {
+		Weight::from_ref_time(34_710_000)
+	}
+	fn vote() -> Weight {
+		Weight::from_ref_time(53_487_000)
+	}
+	fn end_proposal(o: u32, r: u32, ) -> Weight {
+		Weight::from_ref_time(41_530_000)
+			.saturating_add(Weight::from_ref_time(3_160_000).saturating_mul(o.into()))
+			.saturating_add(Weight::from_ref_time(1_214_000).saturating_mul(r.into()))
+	}
+	fn withdraw() -> Weight {
+		Weight::from_ref_time(51_795_000)
+	}
+	fn vote_ranked() -> Weight {
+		Weight::from_ref_time(60_932_000)
+	}
+	fn commit_vote() -> Weight {
+		Weight::from_ref_time(42_310_000)
+	}
+	fn reveal_vote() -> Weight {
+		Weight::from_ref_time(57_960_000)
+	}
+	fn reveal_ranked() -> Weight {
+		Weight::from_ref_time(64_403_000)
+	}
+	fn slash_unrevealed() -> Weight {
+		Weight::from_ref_time(62_018_000)
+	}
+	fn delegate() -> Weight {
+		Weight::from_ref_time(48_139_000)
+	}
+	fn undelegate() -> Weight {
+		Weight::from_ref_time(73_842_000)
+	}
+	fn resolve_tie() -> Weight {
+		Weight::from_ref_time(34_902_000)
+	}
+	fn unvote() -> Weight {
+		Weight::from_ref_time(42_830_000)
+	}
+	fn revote() -> Weight {
+		Weight::from_ref_time(60_372_000)
+	}
+	fn revote_ranked() -> Weight {
+		Weight::from_ref_time(65_119_000)
+	}
+	fn unlock() -> Weight {
+		Weight::from_ref_time(32_417_000)
+	}
+	fn submit_proposal() -> Weight {
+		Weight::from_ref_time(41_270_000)
+	}
+	fn cancel_queued_proposal() -> Weight {
+		Weight::from_ref_time(36_952_000)
+	}
+	fn second() -> Weight {
+		Weight::from_ref_time(34_660_000)
+	}
+	fn cancel_proposal() -> Weight {
+		Weight::from_ref_time(31_508_000)
+	}
+	fn veto() -> Weight {
+		Weight::from_ref_time(15_611_000)
+	}
+	fn unbond() -> Weight {
+		Weight::from_ref_time(53_904_000)
+	}
+	fn withdraw_unbonded() -> Weight {
+		Weight::from_ref_time(28_733_000)
+	}
+	fn top_up_rewards() -> Weight {
+		Weight::from_ref_time(20_318_000)
+	}
+	fn claim_rewards() -> Weight {
+		Weight::from_ref_time(46_105_000)
+	}
+	fn reap_inactive_voter() -> Weight {
+		Weight::from_ref_time(73_418_000)
+	}
+	fn get_asset_votes() -> Weight {
+		Weight::from_ref_time(39_847_000)
+	}
+	fn release_asset_votes() -> Weight {
+		Weight::from_ref_time(41_652_000)
+	}
 }
@@ -38,7 +38,7 @@ pub use frame_support::{
 		},
 		IdentityFee, Weight,
 	},
-	StorageValue,
+	PalletId, StorageValue,
 };
 pub use frame_system::Call as SystemCall;
 pub use pallet_balances::Call as BalancesCall;
@@ -244,7 +244,7 @@ pub const EXISTENTIAL_DEPOSIT: u128 = 500;
 
 impl pallet_balances::Config for Runtime {
 	type MaxLocks = ConstU32<50>;
-	type MaxReserves = ();
+	type MaxReserves = ConstU32<50>;
 	type ReserveIdentifier = [u8; 8];
 	/// The type for recording an account's balance.
 	type Balance = Balance;
@@ -274,19 +274,90 @@ impl pallet_sudo::Config for Runtime {
 	type RuntimeCall = RuntimeCall;
 }
 
+parameter_types! {
+	pub const PreimageBaseDeposit: Balance = 1_000_000_000_000;
+	pub const PreimageByteDeposit: Balance = 1_000_000;
+}
+
+impl pallet_preimage::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_preimage::weights::SubstrateWeight<Runtime>;
+	type Currency = Balances;
+	type ManagerOrigin = frame_system::EnsureRoot<AccountId>;
+	type BaseDeposit = PreimageBaseDeposit;
+	type ByteDeposit = PreimageByteDeposit;
+}
+
 parameter_types! {
 	pub const RegisterFee: u128 = 1_000_000_000_000;
+	pub const UnrevealedSlash: Perbill = Perbill::from_percent(10);
+	pub const InactivitySlash: Perbill = Perbill::from_percent(50);
+	pub const DefaultTieBreak: pallet_voting::TieBreak = pallet_voting::TieBreak::Declare;
+	pub const ProposalDeposit: u128 = 10_000_000_000_000;
+	pub const VotingReserveId: [u8; 8] = *b"vote/stk";
+	pub const RegisterReserveId: [u8; 8] = *b"vote/fee";
+	pub const DepositReserveId: [u8; 8] = *b"vote/dep";
+	pub const UnbondingReserveId: [u8; 8] = *b"vote/unb";
+	pub const VotingPalletId: PalletId = PalletId(*b"py/votng");
+	pub MaxEnactmentWeight: Weight = Perbill::from_percent(50) * BlockWeights::get().max_block;
 }
 
 impl pallet_voting::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Preimages = Preimage;
 	type Currency = Balances;
+	type VotingReserveId = VotingReserveId;
+	type RegisterReserveId = RegisterReserveId;
+	type DepositReserveId = DepositReserveId;
+	type UnbondingReserveId = UnbondingReserveId;
 	type RegisterFee = RegisterFee;
+	type VotePowerCurve = pallet_voting::curve::SquareRoot;
 	type MaxVecLen = ConstU32<3>;
+	type MinProposalDuration = ConstU32<10>;
 	type MaxProposalDuration = ConstU32<1000>;
+	type MaxStartDelay = ConstU32<14400>;
+	type MaxActiveProposals = ConstU32<10>;
+	type MaxFinalizationsPerBlock = ConstU32<2>;
+	type MaxBallotRemovalsPerBlock = ConstU32<512>;
+	type MaxRankings = ConstU32<64>;
+	type RevealPeriod = ConstU32<100>;
+	type UnrevealedSlash = UnrevealedSlash;
+	type Randomness = RandomnessCollectiveFlip;
+	type TieBreakOrigin = frame_system::EnsureRoot<AccountId>;
+	type CancelOrigin = frame_system::EnsureRoot<AccountId>;
+	type VetoOrigin = frame_system::EnsureRoot<AccountId>;
+	type CooldownPeriod = ConstU32<{ 30 * DAYS }>;
+	type DefaultTieBreak = DefaultTieBreak;
+	type EnactmentDelay = ConstU32<10>;
+	type MaxEnactmentWeight = MaxEnactmentWeight;
+	type VoteLockingPeriod = ConstU32<{ 7 * DAYS }>;
+	type MaxDelegationDepth = ConstU32<3>;
+	type MaxDelegators = ConstU32<16>;
+	type MaxTotalDelegators = ConstU32<64>;
+	type ProposalDeposit = ProposalDeposit;
+	type MaxQueuedProposals = ConstU32<32>;
+	type LaunchPeriod = ConstU32<{ DAYS }>;
+	type MaxSeconds = ConstU32<100>;
+	type UnbondingPeriod = ConstU32<{ 7 * DAYS }>;
+	type MaxUnlockingChunks = ConstU32<32>;
+	type PalletId = VotingPalletId;
+	type ProposalReward = ConstU128<1_000_000_000_000>;
+	type MaxMissedProposals = ConstU32<10>;
+	type InactivitySlash = InactivitySlash;
+	type InactivitySlashHandler = pallet_voting::RewardPot<Runtime>;
+	type Assets = pallet_voting::assets::NoAssets<u32, Balance>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type AssetBenchmarkHelper = ();
 	type WeightInfo = pallet_voting::weights::SubstrateWeight<Runtime>;
 }
 
+/// An open proposal of the voting pallet, as returned by the voting runtime API.
+pub type VotingActiveProposal = pallet_voting::CurrentProposal<Runtime>;
+/// A finished proposal of the voting pallet, as returned by the voting runtime API.
+pub type VotingFinishedProposal = pallet_voting::FinishedProposal<Runtime>;
+
 // Create the runtime by composing the FRAME pallets that were previously configured.
 construct_runtime!(
 	pub struct Runtime
@@ -303,6 +374,7 @@ construct_runtime!(
 		Balances: pallet_balances,
 		TransactionPayment: pallet_transaction_payment,
 		Sudo: pallet_sudo,
+		Preimage: pallet_preimage,
 		Voting: pallet_voting,
 	}
 );
@@ -337,8 +409,15 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
+/// Storage migrations run on the next runtime upgrade.
+type Migrations = (
+	pallet_voting::migrations::v1::MigrateToV1<Runtime>,
+	pallet_voting::migrations::v2::MoveToNamedReserves<Runtime>,
+);
+
 #[cfg(feature = "runtime-benchmarks")]
 #[macro_use]
 extern crate frame_benchmarking;
@@ -502,6 +581,26 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_voting_runtime_api::VotingApi<Block, AccountId, VotingActiveProposal, VotingFinishedProposal>
+		for Runtime
+	{
+		fn vote_power(who: AccountId) -> Option<u128> {
+			Voting::get_effective_vote_amount(who)
+		}
+		fn active_proposal_ids() -> Vec<u32> {
+			Voting::get_active_proposal_ids()
+		}
+		fn active_proposal(id: u32) -> Option<VotingActiveProposal> {
+			Voting::get_active_proposal(id)
+		}
+		fn finished_proposals(start: u32, count: u32) -> Vec<VotingFinishedProposal> {
+			Voting::get_closed_proposals(start, count)
+		}
+		fn has_voted(who: AccountId, proposal_id: u32) -> bool {
+			Voting::has_voted(who, proposal_id)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (